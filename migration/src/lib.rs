@@ -1,12 +1,52 @@
 pub use sea_orm_migration::prelude::*;
 
 mod m20220101_000001_create_table;
+mod m20220101_000002_add_work_attempts;
+mod m20220101_000003_add_index_last_updated_at;
+mod m20220101_000004_add_dry_run;
+mod m20220101_000005_add_content_source;
+mod m20220101_000006_add_content_created_at;
+mod m20220101_000007_enable_unaccent;
+mod m20220101_000008_add_work_priority;
+mod m20220101_000009_add_extractor_deleted_at;
+mod m20220101_000010_add_work_created_at;
+mod m20220101_000011_add_repository_deleted_at;
+mod m20220101_000012_add_work_leased_until;
+mod m20220101_000013_create_executors;
+mod m20220101_000014_add_event_bindings_state;
+mod m20220101_000015_add_index_dim;
+mod m20220101_000016_add_index_normalize;
+mod m20220101_000017_add_content_bytes;
+mod m20220101_000018_create_data_connector_state;
+mod m20220101_000019_add_index_distance;
+mod m20220101_000020_add_work_target;
 
 pub struct Migrator;
 
 #[async_trait::async_trait]
 impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
-        vec![Box::new(m20220101_000001_create_table::Migration)]
+        vec![
+            Box::new(m20220101_000001_create_table::Migration),
+            Box::new(m20220101_000002_add_work_attempts::Migration),
+            Box::new(m20220101_000003_add_index_last_updated_at::Migration),
+            Box::new(m20220101_000004_add_dry_run::Migration),
+            Box::new(m20220101_000005_add_content_source::Migration),
+            Box::new(m20220101_000006_add_content_created_at::Migration),
+            Box::new(m20220101_000007_enable_unaccent::Migration),
+            Box::new(m20220101_000008_add_work_priority::Migration),
+            Box::new(m20220101_000009_add_extractor_deleted_at::Migration),
+            Box::new(m20220101_000010_add_work_created_at::Migration),
+            Box::new(m20220101_000011_add_repository_deleted_at::Migration),
+            Box::new(m20220101_000012_add_work_leased_until::Migration),
+            Box::new(m20220101_000013_create_executors::Migration),
+            Box::new(m20220101_000014_add_event_bindings_state::Migration),
+            Box::new(m20220101_000015_add_index_dim::Migration),
+            Box::new(m20220101_000016_add_index_normalize::Migration),
+            Box::new(m20220101_000017_add_content_bytes::Migration),
+            Box::new(m20220101_000018_create_data_connector_state::Migration),
+            Box::new(m20220101_000019_add_index_distance::Migration),
+            Box::new(m20220101_000020_add_work_target::Migration),
+        ]
     }
 }
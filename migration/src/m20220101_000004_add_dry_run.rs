@@ -0,0 +1,146 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Work::Table)
+                    .add_column(
+                        ColumnDef::new(Work::DryRun)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(AttributesIndexDryrun::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(AttributesIndexDryrun::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(AttributesIndexDryrun::RepositoryId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(AttributesIndexDryrun::ExtractorID)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(AttributesIndexDryrun::IndexName)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(AttributesIndexDryrun::Data)
+                            .json_binary()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(AttributesIndexDryrun::ContentId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(AttributesIndexDryrun::CreatedAt)
+                            .big_unsigned()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(IndexChunksDryrun::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(IndexChunksDryrun::ChunkId)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(IndexChunksDryrun::ContentId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(IndexChunksDryrun::Text).text().not_null())
+                    .col(
+                        ColumnDef::new(IndexChunksDryrun::IndexName)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(IndexChunksDryrun::ExtractorName)
+                            .string()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(IndexChunksDryrun::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(AttributesIndexDryrun::Table).to_owned())
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Work::Table)
+                    .drop_column(Work::DryRun)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum Work {
+    Table,
+    DryRun,
+}
+
+#[derive(Iden)]
+enum AttributesIndexDryrun {
+    Table,
+    Id,
+    RepositoryId,
+    ExtractorID,
+    Data,
+    IndexName,
+    ContentId,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+enum IndexChunksDryrun {
+    Table,
+    ChunkId,
+    ContentId,
+    Text,
+    IndexName,
+    ExtractorName,
+}
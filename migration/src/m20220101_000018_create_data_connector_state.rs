@@ -0,0 +1,58 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(DataConnectorState::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(DataConnectorState::RepositoryId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(DataConnectorState::ConnectorId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(DataConnectorState::Cursor)
+                            .json_binary()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(DataConnectorState::UpdatedAt)
+                            .big_unsigned()
+                            .not_null(),
+                    )
+                    .primary_key(
+                        IndexCreateStatement::new()
+                            .col(DataConnectorState::RepositoryId)
+                            .col(DataConnectorState::ConnectorId),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(DataConnectorState::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum DataConnectorState {
+    Table,
+    RepositoryId,
+    ConnectorId,
+    Cursor,
+    UpdatedAt,
+}
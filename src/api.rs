@@ -7,6 +7,7 @@ use smart_default::SmartDefault;
 use strum_macros::{Display, EnumString};
 use utoipa::{IntoParams, ToSchema};
 
+use crate::entity;
 use crate::persistence;
 use crate::vectordbs;
 
@@ -18,6 +19,12 @@ pub enum ExtractorType {
 
     #[serde(rename = "embedding")]
     Attributes { schema: String },
+
+    #[serde(rename = "reranker")]
+    Reranker { model: String },
+
+    #[serde(rename = "classification")]
+    Classification { labels: Vec<String> },
 }
 
 impl From<persistence::ExtractorType> for ExtractorType {
@@ -30,6 +37,10 @@ impl From<persistence::ExtractorType> for ExtractorType {
             persistence::ExtractorType::Attributes { schema } => {
                 ExtractorType::Attributes { schema }
             }
+            persistence::ExtractorType::Reranker { model } => ExtractorType::Reranker { model },
+            persistence::ExtractorType::Classification { labels } => {
+                ExtractorType::Classification { labels }
+            }
         }
     }
 }
@@ -40,12 +51,28 @@ pub enum ExtractorContentType {
     #[serde(rename = "text")]
     #[default]
     Text,
+    #[strum(serialize = "binary")]
+    #[serde(rename = "binary")]
+    Binary,
+    #[strum(serialize = "markdown")]
+    #[serde(rename = "markdown")]
+    Markdown,
+    #[strum(serialize = "html")]
+    #[serde(rename = "html")]
+    Html,
+    #[strum(serialize = "pdf")]
+    #[serde(rename = "pdf")]
+    Pdf,
 }
 
 impl From<persistence::ContentType> for ExtractorContentType {
     fn from(value: persistence::ContentType) -> Self {
         match value {
             persistence::ContentType::Text => ExtractorContentType::Text,
+            persistence::ContentType::Binary => ExtractorContentType::Binary,
+            persistence::ContentType::Markdown => ExtractorContentType::Markdown,
+            persistence::ContentType::Html => ExtractorContentType::Html,
+            persistence::ContentType::Pdf => ExtractorContentType::Pdf,
         }
     }
 }
@@ -54,6 +81,10 @@ impl From<ExtractorContentType> for persistence::ContentType {
     fn from(val: ExtractorContentType) -> Self {
         match val {
             ExtractorContentType::Text => persistence::ContentType::Text,
+            ExtractorContentType::Binary => persistence::ContentType::Binary,
+            ExtractorContentType::Markdown => persistence::ContentType::Markdown,
+            ExtractorContentType::Html => persistence::ContentType::Html,
+            ExtractorContentType::Pdf => persistence::ContentType::Pdf,
         }
     }
 }
@@ -66,85 +97,566 @@ pub enum ExtractorFilter {
         #[serde(flatten)]
         filters: HashMap<String, serde_json::Value>,
     },
+    #[serde(rename = "ieq")]
+    IEq {
+        #[serde(flatten)]
+        filters: HashMap<String, serde_json::Value>,
+    },
     #[serde(rename = "neq")]
     Neq {
         #[serde(flatten)]
         filters: HashMap<String, serde_json::Value>,
     },
+    #[serde(rename = "gt")]
+    Gt {
+        #[serde(flatten)]
+        filters: HashMap<String, serde_json::Value>,
+    },
+    #[serde(rename = "lt")]
+    Lt {
+        #[serde(flatten)]
+        filters: HashMap<String, serde_json::Value>,
+    },
+    #[serde(rename = "gte")]
+    Gte {
+        #[serde(flatten)]
+        filters: HashMap<String, serde_json::Value>,
+    },
+    #[serde(rename = "lte")]
+    Lte {
+        #[serde(flatten)]
+        filters: HashMap<String, serde_json::Value>,
+    },
+    #[serde(rename = "between")]
+    Between {
+        #[serde(flatten)]
+        filters: HashMap<String, ValueRange>,
+    },
+    #[serde(rename = "in")]
+    In {
+        #[serde(flatten)]
+        filters: HashMap<String, serde_json::Value>,
+    },
+    #[serde(rename = "not_in")]
+    NotIn {
+        #[serde(flatten)]
+        filters: HashMap<String, serde_json::Value>,
+    },
+    #[serde(rename = "contains")]
+    Contains {
+        #[serde(flatten)]
+        filters: HashMap<String, String>,
+    },
+    #[serde(rename = "starts_with")]
+    StartsWith {
+        #[serde(flatten)]
+        filters: HashMap<String, String>,
+    },
+    #[serde(rename = "ends_with")]
+    EndsWith {
+        #[serde(flatten)]
+        filters: HashMap<String, String>,
+    },
+    #[serde(rename = "regex")]
+    Regex {
+        #[serde(flatten)]
+        filters: HashMap<String, String>,
+    },
+    #[serde(rename = "exists")]
+    Exists { fields: Vec<String> },
+    #[serde(rename = "not_exists")]
+    NotExists { fields: Vec<String> },
+    #[serde(rename = "and")]
+    And(Vec<ExtractorFilter>),
+    #[serde(rename = "or")]
+    Or(Vec<ExtractorFilter>),
+    #[serde(rename = "not")]
+    #[strum(disabled)]
+    Not(Box<ExtractorFilter>),
+}
+
+/// Inclusive bounds of a `between` filter.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct ValueRange {
+    pub low: serde_json::Value,
+    pub high: serde_json::Value,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ExtractorBinding {
+    /// Assigned by the server, it's ignored when binding an extractor.
+    #[serde(default)]
+    pub id: Option<String>,
     pub extractor_name: String,
     pub index_name: Option<String>,
+    /// Indexes the extractor's output is written to, replaces `index_name` when
+    /// the output goes to more than one index.
+    #[serde(default)]
+    pub output_indexes: Option<Vec<String>>,
     pub filters: Option<Vec<ExtractorFilter>>,
     pub input_params: Option<serde_json::Value>,
+    #[serde(default)]
+    pub null_handling: Option<NullHandling>,
+    #[serde(default)]
+    pub filter_mode: Option<FilterMode>,
+    #[serde(default)]
+    pub target: Option<ExtractionTarget>,
 }
 
 impl From<persistence::ExtractorBinding> for ExtractorBinding {
     fn from(value: persistence::ExtractorBinding) -> Self {
-        let mut eq_filters = HashMap::new();
-        let mut neq_filters = HashMap::new();
-        for filter in value.filters {
-            match filter {
-                persistence::ExtractorFilter::Eq { field, value } => {
-                    eq_filters.insert(field, value);
-                }
-                persistence::ExtractorFilter::Neq { field, value } => {
-                    neq_filters.insert(field, value);
-                }
-            }
-        }
-        let mut filters = vec![];
-        if !eq_filters.is_empty() {
-            filters.push(ExtractorFilter::Eq {
-                filters: eq_filters,
-            });
-        }
-        if !neq_filters.is_empty() {
-            filters.push(ExtractorFilter::Neq {
-                filters: neq_filters,
-            });
-        }
         Self {
+            id: Some(value.id),
             extractor_name: value.extractor_name,
-            index_name: Some(value.index_name),
-            filters: Some(filters),
+            index_name: value.output_indexes.first().cloned(),
+            output_indexes: Some(value.output_indexes),
+            filters: Some(into_api_filters(value.filters)),
             input_params: Some(value.input_params),
+            null_handling: Some(value.null_handling.into()),
+            filter_mode: Some(value.filter_mode.into()),
+            target: Some(value.target.into()),
+        }
+    }
+}
+
+/// How filters treat content whose metadata doesn't have the filtered field.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, SmartDefault)]
+#[serde(rename = "null_handling")]
+pub enum NullHandling {
+    #[serde(rename = "no_match")]
+    #[default]
+    NoMatch,
+    #[serde(rename = "match_as_empty")]
+    MatchAsEmpty,
+}
+
+impl From<persistence::NullHandling> for NullHandling {
+    fn from(value: persistence::NullHandling) -> Self {
+        match value {
+            persistence::NullHandling::NoMatch => NullHandling::NoMatch,
+            persistence::NullHandling::MatchAsEmpty => NullHandling::MatchAsEmpty,
+        }
+    }
+}
+
+impl From<NullHandling> for persistence::NullHandling {
+    fn from(value: NullHandling) -> Self {
+        match value {
+            NullHandling::NoMatch => persistence::NullHandling::NoMatch,
+            NullHandling::MatchAsEmpty => persistence::NullHandling::MatchAsEmpty,
+        }
+    }
+}
+
+/// Whether content has to pass all of a binding's filters, or any of them.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, SmartDefault)]
+#[serde(rename = "filter_mode")]
+pub enum FilterMode {
+    #[serde(rename = "all")]
+    #[default]
+    All,
+    #[serde(rename = "any")]
+    Any,
+}
+
+impl From<persistence::FilterMode> for FilterMode {
+    fn from(value: persistence::FilterMode) -> Self {
+        match value {
+            persistence::FilterMode::All => FilterMode::All,
+            persistence::FilterMode::Any => FilterMode::Any,
+        }
+    }
+}
+
+impl From<FilterMode> for persistence::FilterMode {
+    fn from(value: FilterMode) -> Self {
+        match value {
+            FilterMode::All => persistence::FilterMode::All,
+            FilterMode::Any => persistence::FilterMode::Any,
+        }
+    }
+}
+
+/// What a binding extracts from, the repository's content or its events.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, SmartDefault)]
+#[serde(rename = "extraction_target")]
+pub enum ExtractionTarget {
+    #[serde(rename = "content")]
+    #[default]
+    Content,
+    #[serde(rename = "events")]
+    Events,
+}
+
+impl From<persistence::ExtractionTarget> for ExtractionTarget {
+    fn from(value: persistence::ExtractionTarget) -> Self {
+        match value {
+            persistence::ExtractionTarget::Content => ExtractionTarget::Content,
+            persistence::ExtractionTarget::Events => ExtractionTarget::Events,
+        }
+    }
+}
+
+impl From<ExtractionTarget> for persistence::ExtractionTarget {
+    fn from(value: ExtractionTarget) -> Self {
+        match value {
+            ExtractionTarget::Content => persistence::ExtractionTarget::Content,
+            ExtractionTarget::Events => persistence::ExtractionTarget::Events,
+        }
+    }
+}
+
+// Groups the filters by operator, nested groups are converted recursively
+fn into_api_filters(value: Vec<persistence::ExtractorFilter>) -> Vec<ExtractorFilter> {
+    let mut eq_filters = HashMap::new();
+    let mut ieq_filters = HashMap::new();
+    let mut neq_filters = HashMap::new();
+    let mut gt_filters = HashMap::new();
+    let mut lt_filters = HashMap::new();
+    let mut gte_filters = HashMap::new();
+    let mut lte_filters = HashMap::new();
+    let mut between_filters = HashMap::new();
+    let mut in_filters = HashMap::new();
+    let mut not_in_filters = HashMap::new();
+    let mut contains_filters = HashMap::new();
+    let mut starts_with_filters = HashMap::new();
+    let mut ends_with_filters = HashMap::new();
+    let mut regex_filters = HashMap::new();
+    let mut exists_fields = vec![];
+    let mut not_exists_fields = vec![];
+    let mut groups = vec![];
+    for filter in value {
+        match filter {
+            persistence::ExtractorFilter::Eq { field, value } => {
+                eq_filters.insert(field, value);
+            }
+            persistence::ExtractorFilter::IEq { field, value } => {
+                ieq_filters.insert(field, value);
+            }
+            persistence::ExtractorFilter::Neq { field, value } => {
+                neq_filters.insert(field, value);
+            }
+            persistence::ExtractorFilter::Gt { field, value } => {
+                gt_filters.insert(field, value);
+            }
+            persistence::ExtractorFilter::Lt { field, value } => {
+                lt_filters.insert(field, value);
+            }
+            persistence::ExtractorFilter::Gte { field, value } => {
+                gte_filters.insert(field, value);
+            }
+            persistence::ExtractorFilter::Lte { field, value } => {
+                lte_filters.insert(field, value);
+            }
+            persistence::ExtractorFilter::Between { field, low, high } => {
+                between_filters.insert(field, ValueRange { low, high });
+            }
+            persistence::ExtractorFilter::In { field, values } => {
+                in_filters.insert(field, serde_json::Value::Array(values));
+            }
+            persistence::ExtractorFilter::NotIn { field, values } => {
+                not_in_filters.insert(field, serde_json::Value::Array(values));
+            }
+            persistence::ExtractorFilter::Contains { field, substring } => {
+                contains_filters.insert(field, substring);
+            }
+            persistence::ExtractorFilter::StartsWith { field, prefix } => {
+                starts_with_filters.insert(field, prefix);
+            }
+            persistence::ExtractorFilter::EndsWith { field, suffix } => {
+                ends_with_filters.insert(field, suffix);
+            }
+            persistence::ExtractorFilter::Regex { field, pattern } => {
+                regex_filters.insert(field, pattern);
+            }
+            persistence::ExtractorFilter::Exists { field } => {
+                exists_fields.push(field);
+            }
+            persistence::ExtractorFilter::NotExists { field } => {
+                not_exists_fields.push(field);
+            }
+            persistence::ExtractorFilter::And(filters) => {
+                groups.push(ExtractorFilter::And(into_api_filters(filters)));
+            }
+            persistence::ExtractorFilter::Or(filters) => {
+                groups.push(ExtractorFilter::Or(into_api_filters(filters)));
+            }
+            persistence::ExtractorFilter::Not(filter) => {
+                let mut filters = into_api_filters(vec![*filter]);
+                let filter = match filters.len() {
+                    1 => filters.remove(0),
+                    _ => ExtractorFilter::And(filters),
+                };
+                groups.push(ExtractorFilter::Not(Box::new(filter)));
+            }
         }
     }
+    let mut filters = vec![];
+    if !eq_filters.is_empty() {
+        filters.push(ExtractorFilter::Eq {
+            filters: eq_filters,
+        });
+    }
+    if !ieq_filters.is_empty() {
+        filters.push(ExtractorFilter::IEq {
+            filters: ieq_filters,
+        });
+    }
+    if !neq_filters.is_empty() {
+        filters.push(ExtractorFilter::Neq {
+            filters: neq_filters,
+        });
+    }
+    if !gt_filters.is_empty() {
+        filters.push(ExtractorFilter::Gt {
+            filters: gt_filters,
+        });
+    }
+    if !lt_filters.is_empty() {
+        filters.push(ExtractorFilter::Lt {
+            filters: lt_filters,
+        });
+    }
+    if !gte_filters.is_empty() {
+        filters.push(ExtractorFilter::Gte {
+            filters: gte_filters,
+        });
+    }
+    if !lte_filters.is_empty() {
+        filters.push(ExtractorFilter::Lte {
+            filters: lte_filters,
+        });
+    }
+    if !between_filters.is_empty() {
+        filters.push(ExtractorFilter::Between {
+            filters: between_filters,
+        });
+    }
+    if !in_filters.is_empty() {
+        filters.push(ExtractorFilter::In {
+            filters: in_filters,
+        });
+    }
+    if !not_in_filters.is_empty() {
+        filters.push(ExtractorFilter::NotIn {
+            filters: not_in_filters,
+        });
+    }
+    if !contains_filters.is_empty() {
+        filters.push(ExtractorFilter::Contains {
+            filters: contains_filters,
+        });
+    }
+    if !starts_with_filters.is_empty() {
+        filters.push(ExtractorFilter::StartsWith {
+            filters: starts_with_filters,
+        });
+    }
+    if !ends_with_filters.is_empty() {
+        filters.push(ExtractorFilter::EndsWith {
+            filters: ends_with_filters,
+        });
+    }
+    if !regex_filters.is_empty() {
+        filters.push(ExtractorFilter::Regex {
+            filters: regex_filters,
+        });
+    }
+    if !exists_fields.is_empty() {
+        filters.push(ExtractorFilter::Exists {
+            fields: exists_fields,
+        });
+    }
+    if !not_exists_fields.is_empty() {
+        filters.push(ExtractorFilter::NotExists {
+            fields: not_exists_fields,
+        });
+    }
+    filters.extend(groups);
+    filters
+}
+
+// Membership filters accept either a list of values or a single value
+fn into_filter_values(value: serde_json::Value) -> Vec<serde_json::Value> {
+    match value {
+        serde_json::Value::Array(values) => values,
+        value => vec![value],
+    }
 }
 
 pub fn into_persistence_extractor_binding(
     repository: &str,
     extractor_binding: ExtractorBinding,
 ) -> persistence::ExtractorBinding {
+    let output_indexes = extractor_binding
+        .output_indexes
+        .filter(|output_indexes| !output_indexes.is_empty());
+    let index_name = output_indexes
+        .as_ref()
+        .map(|output_indexes| output_indexes[0].clone())
+        .or(extractor_binding.index_name)
+        .unwrap_or(extractor_binding.extractor_name.clone());
+    let binding = persistence::ExtractorBinding::new(
+        repository,
+        extractor_binding.extractor_name.clone(),
+        index_name,
+        into_persistence_filters(extractor_binding.filters.unwrap_or_default()),
+        extractor_binding
+            .input_params
+            .unwrap_or(serde_json::json!({})),
+    )
+    .with_null_handling(extractor_binding.null_handling.unwrap_or_default().into())
+    .with_filter_mode(extractor_binding.filter_mode.unwrap_or_default().into())
+    .with_target(extractor_binding.target.unwrap_or_default().into());
+    match output_indexes {
+        Some(output_indexes) => binding.with_output_indexes(output_indexes),
+        None => binding,
+    }
+}
+
+/// Sets the metadata `key` to `value` on the content matching all the filters.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TagRule {
+    pub filters: Vec<ExtractorFilter>,
+    pub key: String,
+    pub value: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ApplyTagRulesRequest {
+    pub rules: Vec<TagRule>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ApplyTagRulesResponse {
+    /// The number of content tagged by each rule, in the order of the rules.
+    pub tagged: Vec<u64>,
+}
+
+pub fn into_persistence_tag_rules(
+    rules: Vec<TagRule>,
+) -> Vec<(Vec<persistence::ExtractorFilter>, String, serde_json::Value)> {
+    rules
+        .into_iter()
+        .map(|rule| (into_persistence_filters(rule.filters), rule.key, rule.value))
+        .collect()
+}
+
+fn into_persistence_filters(filters: Vec<ExtractorFilter>) -> Vec<persistence::ExtractorFilter> {
     let mut extraction_filters = vec![];
-    for filter in extractor_binding.filters.unwrap_or_default() {
+    for filter in filters {
         match filter {
             ExtractorFilter::Eq { filters } => {
                 for (field, value) in filters {
                     extraction_filters.push(persistence::ExtractorFilter::Eq { field, value });
                 }
             }
+            ExtractorFilter::IEq { filters } => {
+                for (field, value) in filters {
+                    extraction_filters.push(persistence::ExtractorFilter::IEq { field, value });
+                }
+            }
             ExtractorFilter::Neq { filters } => {
                 for (field, value) in filters {
                     extraction_filters.push(persistence::ExtractorFilter::Neq { field, value });
                 }
             }
+            ExtractorFilter::Gt { filters } => {
+                for (field, value) in filters {
+                    extraction_filters.push(persistence::ExtractorFilter::Gt { field, value });
+                }
+            }
+            ExtractorFilter::Lt { filters } => {
+                for (field, value) in filters {
+                    extraction_filters.push(persistence::ExtractorFilter::Lt { field, value });
+                }
+            }
+            ExtractorFilter::Gte { filters } => {
+                for (field, value) in filters {
+                    extraction_filters.push(persistence::ExtractorFilter::Gte { field, value });
+                }
+            }
+            ExtractorFilter::Lte { filters } => {
+                for (field, value) in filters {
+                    extraction_filters.push(persistence::ExtractorFilter::Lte { field, value });
+                }
+            }
+            ExtractorFilter::Between { filters } => {
+                for (field, ValueRange { low, high }) in filters {
+                    extraction_filters.push(persistence::ExtractorFilter::Between {
+                        field,
+                        low,
+                        high,
+                    });
+                }
+            }
+            ExtractorFilter::In { filters } => {
+                for (field, values) in filters {
+                    let values = into_filter_values(values);
+                    extraction_filters.push(persistence::ExtractorFilter::In { field, values });
+                }
+            }
+            ExtractorFilter::NotIn { filters } => {
+                for (field, values) in filters {
+                    let values = into_filter_values(values);
+                    extraction_filters.push(persistence::ExtractorFilter::NotIn { field, values });
+                }
+            }
+            ExtractorFilter::Contains { filters } => {
+                for (field, substring) in filters {
+                    extraction_filters
+                        .push(persistence::ExtractorFilter::Contains { field, substring });
+                }
+            }
+            ExtractorFilter::StartsWith { filters } => {
+                for (field, prefix) in filters {
+                    extraction_filters
+                        .push(persistence::ExtractorFilter::StartsWith { field, prefix });
+                }
+            }
+            ExtractorFilter::EndsWith { filters } => {
+                for (field, suffix) in filters {
+                    extraction_filters
+                        .push(persistence::ExtractorFilter::EndsWith { field, suffix });
+                }
+            }
+            ExtractorFilter::Regex { filters } => {
+                for (field, pattern) in filters {
+                    extraction_filters.push(persistence::ExtractorFilter::Regex { field, pattern });
+                }
+            }
+            ExtractorFilter::Exists { fields } => {
+                for field in fields {
+                    extraction_filters.push(persistence::ExtractorFilter::Exists { field });
+                }
+            }
+            ExtractorFilter::NotExists { fields } => {
+                for field in fields {
+                    extraction_filters.push(persistence::ExtractorFilter::NotExists { field });
+                }
+            }
+            ExtractorFilter::And(filters) => {
+                extraction_filters.push(persistence::ExtractorFilter::And(
+                    into_persistence_filters(filters),
+                ));
+            }
+            ExtractorFilter::Or(filters) => {
+                extraction_filters.push(persistence::ExtractorFilter::Or(
+                    into_persistence_filters(filters),
+                ));
+            }
+            // An API filter can expand to several fields, which are negated together
+            ExtractorFilter::Not(filter) => {
+                let mut filters = into_persistence_filters(vec![*filter]);
+                let filter = match filters.len() {
+                    1 => filters.remove(0),
+                    _ => persistence::ExtractorFilter::And(filters),
+                };
+                extraction_filters.push(persistence::ExtractorFilter::Not(Box::new(filter)));
+            }
         }
     }
-    persistence::ExtractorBinding::new(
-        repository,
-        extractor_binding.extractor_name.clone(),
-        extractor_binding
-            .index_name
-            .unwrap_or(extractor_binding.extractor_name.clone()),
-        extraction_filters,
-        extractor_binding
-            .input_params
-            .unwrap_or(serde_json::json!({})),
-    )
+    extraction_filters
 }
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -178,6 +690,22 @@ pub enum SourceType {
     // todo: replace metadata with actual request parameters for gmail API
     #[serde(rename = "gmail")]
     Gmail { metadata: Option<String> },
+    #[serde(rename = "s3")]
+    S3 {
+        bucket: String,
+        prefix: Option<String>,
+        region: String,
+    },
+    #[serde(rename = "local_fs")]
+    LocalFs { path: String, glob: Option<String> },
+    #[serde(rename = "web_page")]
+    WebPage {
+        url: String,
+        depth: u8,
+        /// The most pages a crawl reads, 100 when not given.
+        #[serde(default)]
+        max_pages: Option<usize>,
+    },
 }
 
 impl From<SourceType> for persistence::SourceType {
@@ -187,6 +715,25 @@ impl From<SourceType> for persistence::SourceType {
                 persistence::SourceType::GoogleContact { metadata }
             }
             SourceType::Gmail { metadata } => persistence::SourceType::Gmail { metadata },
+            SourceType::S3 {
+                bucket,
+                prefix,
+                region,
+            } => persistence::SourceType::S3 {
+                bucket,
+                prefix,
+                region,
+            },
+            SourceType::LocalFs { path, glob } => persistence::SourceType::LocalFs { path, glob },
+            SourceType::WebPage {
+                url,
+                depth,
+                max_pages,
+            } => persistence::SourceType::WebPage {
+                url,
+                depth,
+                max_pages,
+            },
         }
     }
 }
@@ -195,12 +742,17 @@ impl From<SourceType> for persistence::SourceType {
 #[serde(rename = "data_connector")]
 pub struct DataConnector {
     pub source: SourceType,
+    /// How often the connector syncs, or `None` when it's only synced on request.
+    #[serde(default)]
+    pub sync_interval_secs: Option<u64>,
 }
 
 impl From<DataConnector> for persistence::DataConnector {
     fn from(value: DataConnector) -> Self {
         Self {
             source: value.source.into(),
+            sync_interval_secs: value.sync_interval_secs,
+            last_sync: None,
         }
     }
 }
@@ -220,6 +772,218 @@ pub struct GetRepositoryResponse {
     pub repository: DataRepository,
 }
 
+/// What a deletion removed, as the number of rows of each kind.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct DeletionReport {
+    pub content: u64,
+    pub chunks: u64,
+    pub attributes: u64,
+    pub vector_chunks: u64,
+    pub work: u64,
+}
+
+impl From<persistence::DeletionReport> for DeletionReport {
+    fn from(value: persistence::DeletionReport) -> Self {
+        Self {
+            content: value.content,
+            chunks: value.chunks,
+            attributes: value.attributes,
+            vector_chunks: value.vector_chunks,
+            work: value.work,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, IntoParams, ToSchema)]
+pub struct DeleteRepositoryRequest {
+    /// Removes the repository and its data for good. Otherwise the repository is
+    /// only hidden, and can be restored.
+    #[serde(default)]
+    pub purge: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DeleteRepositoryResponse {
+    /// What was removed when the repository was purged.
+    pub deleted: Option<DeletionReport>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct RestoreRepositoryResponse {}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct UpdateRepositoryMetadataRequest {
+    /// Replaces all the metadata of the repository.
+    pub metadata: HashMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct UpdateRepositoryMetadataResponse {}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DeleteContentResponse {
+    pub deleted: DeletionReport,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Index {
+    pub name: String,
+    pub extractor_name: String,
+    pub index_type: String,
+    /// The dimension of the embeddings of a vector index.
+    pub dim: Option<i64>,
+    pub distance: Option<String>,
+}
+
+impl From<entity::index::Model> for Index {
+    fn from(value: entity::index::Model) -> Self {
+        Self {
+            name: value.name,
+            extractor_name: value.extractor_name,
+            index_type: value.index_type,
+            dim: value.dim,
+            distance: value.distance,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ListIndexesResponse {
+    pub indexes: Vec<Index>,
+}
+
+/// An estimate of the space the vectors of an index take in the vector store.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct IndexSizeResponse {
+    pub chunks: u64,
+    pub dim: u64,
+    pub vector_bytes: u64,
+    pub overhead_bytes: u64,
+    pub total_bytes: u64,
+}
+
+impl From<persistence::SizeEstimate> for IndexSizeResponse {
+    fn from(value: persistence::SizeEstimate) -> Self {
+        Self {
+            chunks: value.chunks,
+            dim: value.dim,
+            vector_bytes: value.vector_bytes,
+            overhead_bytes: value.overhead_bytes,
+            total_bytes: value.total_bytes,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DeleteIndexResponse {
+    pub deleted: DeletionReport,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, IntoParams, ToSchema)]
+pub struct ReconcileIndexRequest {
+    /// Queues the content of chunks missing from the vector store to be
+    /// extracted again.
+    #[serde(default)]
+    pub repair: bool,
+}
+
+/// How the chunks of an index compare with the vectors held for it in the
+/// vector store.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct ReconcileIndexResponse {
+    pub db_chunks: u64,
+    pub store_vectors: u64,
+    pub missing_in_store: Vec<String>,
+    pub extra_in_store: Vec<String>,
+}
+
+impl From<persistence::ReconcileReport> for ReconcileIndexResponse {
+    fn from(value: persistence::ReconcileReport) -> Self {
+        Self {
+            db_chunks: value.db_chunks,
+            store_vectors: value.store_vectors,
+            missing_in_store: value.missing_in_store,
+            extra_in_store: value.extra_in_store,
+        }
+    }
+}
+
+/// A configuration problem which keeps a binding from producing anything.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename = "config_warning")]
+pub enum ConfigWarning {
+    #[serde(rename = "missing_extractor")]
+    MissingExtractor { binding: String, extractor: String },
+    #[serde(rename = "index_dim_mismatch")]
+    IndexDimMismatch {
+        index: String,
+        extractor_dim: u64,
+        index_dim: u64,
+    },
+    #[serde(rename = "unknown_filter_field")]
+    UnknownFilterField { binding: String, field: String },
+    #[serde(rename = "missing_vector_index")]
+    MissingVectorIndex { index: String },
+}
+
+impl From<persistence::ConfigWarning> for ConfigWarning {
+    fn from(value: persistence::ConfigWarning) -> Self {
+        match value {
+            persistence::ConfigWarning::MissingExtractor { binding, extractor } => {
+                ConfigWarning::MissingExtractor { binding, extractor }
+            }
+            persistence::ConfigWarning::IndexDimMismatch {
+                index,
+                extractor_dim,
+                index_dim,
+            } => ConfigWarning::IndexDimMismatch {
+                index,
+                extractor_dim,
+                index_dim,
+            },
+            persistence::ConfigWarning::UnknownFilterField { binding, field } => {
+                ConfigWarning::UnknownFilterField { binding, field }
+            }
+            persistence::ConfigWarning::MissingVectorIndex { index } => {
+                ConfigWarning::MissingVectorIndex { index }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ValidateRepositoryResponse {
+    pub warnings: Vec<ConfigWarning>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RepositoryStatsResponse {
+    /// Age in seconds of the oldest content each binding hasn't processed yet,
+    /// null when the binding is caught up.
+    pub binding_lag: HashMap<String, Option<u64>>,
+    /// The fraction of the content which every binding it passes the filters of
+    /// has processed.
+    pub extraction_progress: f32,
+    pub content_count: u64,
+    /// The number of content of each content type.
+    pub content_count_by_type: HashMap<String, u64>,
+    /// The number of content ingested from each source, content added without a
+    /// source is counted under `unknown`.
+    pub content_count_by_source: HashMap<String, u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, IntoParams, ToSchema)]
+pub struct ListRepositoriesRequest {
+    /// Only lists the repositories whose metadata has this key set to
+    /// `metadata_value`.
+    pub metadata_key: Option<String>,
+    /// Read as JSON, or as a string when it isn't valid JSON.
+    pub metadata_value: Option<String>,
+    /// Also lists the repositories which were deleted but not purged.
+    #[serde(default)]
+    pub include_deleted: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ListRepositoriesResponse {
     pub repositories: Vec<DataRepository>,
@@ -272,11 +1036,42 @@ pub struct ExtractorBindRequest {
 #[derive(Debug, Serialize, Deserialize, Default, ToSchema)]
 pub struct ExtractorBindResponse {}
 
+#[derive(Debug, Serialize, Deserialize, Default, IntoParams, ToSchema)]
+pub struct RemoveExtractorBindingRequest {
+    /// Also drops the record of which content the binding has processed.
+    #[serde(default)]
+    pub clear_state: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, ToSchema)]
+pub struct RemoveExtractorBindingResponse {}
+
+#[derive(Debug, Serialize, Deserialize, Default, ToSchema)]
+pub struct IndexBindingResponse {
+    /// The binding writing to the index, null when none does.
+    pub binding: Option<ExtractorBinding>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, ToSchema)]
+pub struct UpdateBindingParamsRequest {
+    pub input_params: serde_json::Value,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, ToSchema)]
+pub struct UpdateBindingParamsResponse {}
+
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct Text {
     pub text: String,
     #[serde(default)]
     pub metadata: HashMap<String, serde_json::Value>,
+    /// Together with `external_id`, identifies the document in the system it
+    /// came from, so redelivering it updates the stored text.
+    pub source: Option<String>,
+    pub external_id: Option<String>,
+    /// Priority of the work extracting from this text, higher runs first.
+    #[serde(default)]
+    pub priority: i32,
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -288,6 +1083,11 @@ pub struct TextAddRequest {
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct RunExtractorsResponse {}
 
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SyncConnectorsResponse {
+    pub content_ids: Vec<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ExtractorConfig {
     pub name: String,
@@ -311,6 +1111,17 @@ pub struct Executor {
     pub extractors: Vec<ExtractorConfig>,
 }
 
+impl From<persistence::Executor> for Executor {
+    fn from(value: persistence::Executor) -> Self {
+        let extractors: Vec<persistence::ExtractorConfig> =
+            serde_json::from_value(value.capabilities).unwrap_or_default();
+        Self {
+            id: value.id,
+            extractors: extractors.into_iter().map(|e| e.into()).collect(),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ListExecutorsResponse {
     pub executors: Vec<Executor>,
@@ -321,9 +1132,24 @@ pub struct ListExtractorsResponse {
     pub extractors: Vec<ExtractorConfig>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Default, IntoParams, ToSchema)]
+pub struct ListExtractorsRequest {
+    /// Also lists the extractors which were deleted.
+    #[serde(default)]
+    pub include_deleted: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, ToSchema)]
+pub struct DeleteExtractorResponse {}
+
 #[derive(Debug, Serialize, Deserialize, Default, ToSchema)]
 pub struct TextAdditionResponse {}
 
+#[derive(Debug, Serialize, Deserialize, Default, ToSchema)]
+pub struct ContentAdditionResponse {
+    pub id: String,
+}
+
 #[derive(Debug, Serialize, Deserialize, IntoParams, ToSchema)]
 pub struct SearchRequest {
     pub index: String,
@@ -331,6 +1157,103 @@ pub struct SearchRequest {
     pub k: Option<u64>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Content {
+    pub id: String,
+    pub text: String,
+    pub metadata: HashMap<String, serde_json::Value>,
+    pub content_type: ExtractorContentType,
+}
+
+impl From<persistence::Content<String>> for Content {
+    fn from(value: persistence::Content<String>) -> Self {
+        Self {
+            id: value.id,
+            text: value.content,
+            metadata: value.metadata,
+            content_type: value.content_type.into(),
+        }
+    }
+}
+
+/// The order content is listed in. Content in natural order can't be paged.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, SmartDefault)]
+#[serde(rename = "content_order")]
+pub enum ContentOrder {
+    #[serde(rename = "by_id")]
+    #[default]
+    ById,
+    #[serde(rename = "by_created_at")]
+    ByCreatedAt,
+    #[serde(rename = "natural")]
+    Natural,
+}
+
+impl From<ContentOrder> for persistence::ContentOrder {
+    fn from(value: ContentOrder) -> Self {
+        match value {
+            ContentOrder::ById => persistence::ContentOrder::ById,
+            ContentOrder::ByCreatedAt => persistence::ContentOrder::ByCreatedAt,
+            ContentOrder::Natural => persistence::ContentOrder::Natural,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, IntoParams, ToSchema)]
+pub struct ListContentRequest {
+    pub limit: Option<u64>,
+    /// Lists the content after the one with this id, to page through content.
+    pub after_id: Option<String>,
+    #[serde(default)]
+    pub order: ContentOrder,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ListContentResponse {
+    pub content: Vec<Content>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PatchContentMetadataResponse {}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ContentByIdsRequest {
+    /// Ids which aren't in the repository are skipped.
+    pub ids: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, IntoParams, ToSchema)]
+pub struct ContentSearchRequest {
+    pub query: String,
+    pub offset: Option<u64>,
+    pub limit: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ContentSearchResult {
+    pub id: String,
+    pub repository: Option<String>,
+    pub text: String,
+    pub metadata: HashMap<String, serde_json::Value>,
+}
+
+impl From<persistence::Content<String>> for ContentSearchResult {
+    fn from(value: persistence::Content<String>) -> Self {
+        Self {
+            id: value.id,
+            repository: value.repository_id,
+            text: value.content,
+            metadata: value.metadata,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ContentSearchResponse {
+    pub results: Vec<ContentSearchResult>,
+    pub total: u64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ExtractedAttributes {
     pub id: String,
@@ -356,6 +1279,24 @@ pub struct AttributeLookupRequest {
     pub index: String,
 }
 
+#[derive(Debug, Serialize, Deserialize, Default, ToSchema)]
+pub struct ContentAttributesRequest {
+    pub content_ids: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, ToSchema)]
+pub struct ContentAttributesResponse {
+    /// The attributes of each content from every index, content without any
+    /// attributes is left out.
+    #[schema(value_type = Object)]
+    pub attributes: HashMap<String, Vec<ExtractedAttributes>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, ToSchema)]
+pub struct DeleteAttributesResponse {
+    pub deleted: u64,
+}
+
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct AttributeLookupResponse {
     pub attributes: Vec<ExtractedAttributes>,
@@ -392,11 +1333,27 @@ pub struct EventAddRequest {
 #[derive(Serialize, Deserialize, ToSchema)]
 pub struct EventAddResponse {}
 
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct EventStreamResponse {
+    pub added: u64,
+}
+
 #[derive(Serialize, Deserialize, ToSchema)]
 pub struct ListEventsResponse {
     pub messages: Vec<Event>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Default, ToSchema)]
+pub struct DeleteEventsRequest {
+    /// The events to delete, all the events of the repository when not given.
+    pub ids: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, ToSchema)]
+pub struct DeleteEventsResponse {
+    pub deleted: u64,
+}
+
 #[derive(Debug, Serialize, Deserialize, Default, ToSchema)]
 pub struct DocumentFragment {
     pub text: String,
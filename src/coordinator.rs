@@ -1,5 +1,13 @@
-use axum::{extract::State, http::StatusCode, routing::get, routing::post, Json, Router};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::delete,
+    routing::get,
+    routing::post,
+    Json, Router,
+};
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use tokio::{
     signal,
     sync::mpsc::{self, Receiver, Sender},
@@ -9,11 +17,14 @@ use tracing::{error, info};
 use crate::{
     api::IndexifyAPIError,
     persistence::{
-        ExtractionEventPayload, ExtractorBinding, ExtractorConfig, Repository, Work, WorkState,
+        ContentOrder, DryRunResults, ExtractionEventPayload, ExtractionTarget, ExtractorBinding,
+        ExtractorConfig, Repository, RepositoryError, Work, WorkState, EXECUTOR_HEARTBEAT_TIMEOUT,
+        MAX_WORK_ATTEMPTS,
     },
     ServerConfig,
 };
-use indexmap::{IndexMap, IndexSet};
+use futures::TryStreamExt;
+use indexmap::IndexSet;
 use std::{
     collections::HashMap,
     net::SocketAddr,
@@ -59,11 +70,61 @@ pub struct CreateWork {
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct CreateWorkResponse {}
 
-pub struct Coordinator {
-    // Executor ID -> Last Seen Timestamp
-    executor_health_checks: Arc<RwLock<IndexMap<String, u64>>>,
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct ListWorkParams {
+    pub state: WorkState,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct ListWorkResponse {
+    pub work: Vec<Work>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct WorkStatsResponse {
+    pub stats: HashMap<WorkState, u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct RetryFailedWorkResponse {
+    pub requeued: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct ImportWorkQueueResponse {}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct CancelWorkResponse {}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct DeleteWorkResponse {}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct MigrateBindingStateResponse {
+    pub migrated: u64,
+}
 
-    // List of known executors
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct DryRunRequest {
+    pub extractor: String,
+    pub index_name: String,
+    #[serde(default)]
+    pub input_params: Option<serde_json::Value>,
+    pub content_ids: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct DryRunResponse {
+    pub work_ids: Vec<String>,
+}
+
+// How often processed extraction events past their retention are deleted, and
+// how many are deleted at a time
+const EVENT_PRUNE_INTERVAL_SECS: u64 = 3600;
+const EVENT_PRUNE_BATCH_SIZE: u64 = 1000;
+
+pub struct Coordinator {
+    // Executors registered since the coordinator started
     executors: Arc<RwLock<IndexSet<String>>>,
 
     repository: Arc<Repository>,
@@ -76,7 +137,6 @@ impl Coordinator {
         let (tx, rx) = mpsc::channel(32);
 
         let coordinator = Arc::new(Self {
-            executor_health_checks: Arc::new(RwLock::new(IndexMap::new())),
             executors: Arc::new(RwLock::new(IndexSet::new())),
             repository,
             tx,
@@ -89,35 +149,74 @@ impl Coordinator {
     }
 
     pub async fn record_executor(&self, worker: ExecutorInfo) -> Result<(), anyhow::Error> {
-        self.executor_health_checks
-            .write()
-            .unwrap()
-            .insert(worker.id.clone(), worker.last_seen);
+        // An executor reports what it can run when it's first seen, later syncs
+        // only count as heartbeats
+        let heartbeat = if self.executors.read().unwrap().contains(&worker.id) {
+            self.repository.heartbeat_executor(&worker.id).await
+        } else {
+            Err(RepositoryError::ExecutorNotFound(worker.id.clone()))
+        };
+        match heartbeat {
+            Err(RepositoryError::ExecutorNotFound(_)) => {
+                self.repository
+                    .register_executor(&worker.id, json!(worker.available_extractors))
+                    .await?
+            }
+            heartbeat => heartbeat?,
+        }
 
-        // Add the worker to our list of active workers
         self.executors.write().unwrap().insert(worker.id);
         Ok(())
     }
 
+    /// The executors which sent a heartbeat recently enough to be given work.
+    pub async fn live_executors(&self) -> Result<Vec<ExecutorInfo>, anyhow::Error> {
+        let executors = self
+            .repository
+            .list_live_executors(EXECUTOR_HEARTBEAT_TIMEOUT)
+            .await?
+            .into_iter()
+            .map(|executor| ExecutorInfo {
+                id: executor.id,
+                last_seen: executor.last_heartbeat,
+                available_extractors: serde_json::from_value(executor.capabilities)
+                    .unwrap_or_default(),
+            })
+            .collect();
+        Ok(executors)
+    }
+
     pub async fn process_extraction_events(&self) -> Result<(), anyhow::Error> {
         let events = self.repository.unprocessed_extraction_events().await?;
         for event in &events {
             info!("processing extraction event: {}", event.id);
             match &event.payload {
                 ExtractionEventPayload::ExtractorBindingAdded { repository, id } => {
-                    let binding = self.repository.binding_by_id(repository, id).await?;
-                    self.generate_work_for_extractor_bindings(repository, &binding)
-                        .await?;
+                    match self.repository.binding_by_id(repository, id).await {
+                        Ok(binding) => {
+                            self.generate_work_for_extractor_bindings(repository, &binding)
+                                .await?;
+                        }
+                        // The binding was removed before its work was generated
+                        Err(RepositoryError::ExtractorBindingNotFound(_)) => {}
+                        Err(err) => return Err(err.into()),
+                    }
                 }
-                ExtractionEventPayload::CreateContent { content_id } => {
+                ExtractionEventPayload::ExtractorBindingRemoved { .. } => {}
+                ExtractionEventPayload::CreateContent {
+                    content_id,
+                    priority,
+                } => {
                     if let Err(err) = self
-                        .create_work(&event.repository_id, Some(content_id))
+                        .create_work(&event.repository_id, Some(content_id), *priority)
                         .await
                     {
                         error!("unable to create work: {}", &err.to_string());
                         return Err(err);
                     }
                 }
+                // Whatever was extracted from the content was deleted along with it
+                ExtractionEventPayload::ContentDeleted { .. } => {}
             };
 
             self.repository
@@ -132,35 +231,50 @@ impl Coordinator {
         repository: &str,
         extractor_binding: &ExtractorBinding,
     ) -> Result<(), anyhow::Error> {
+        if extractor_binding.target == ExtractionTarget::Events {
+            return self.create_event_work(repository, extractor_binding).await;
+        }
         let content_list = self
             .repository
             .content_with_unapplied_extractor(repository, extractor_binding, None)
             .await?;
         for content in content_list {
-            self.create_work(repository, Some(&content.id)).await?;
+            self.create_work(repository, Some(&content.id), 0).await?;
         }
         Ok(())
     }
 
     pub async fn distribute_work(&self) -> Result<(), anyhow::Error> {
-        let unallocated_work = self.repository.unallocated_work().await?;
-
-        // work_id -> executor_id
-        let mut work_assignment = HashMap::new();
-        for work in unallocated_work {
-            let rand_index = rand::random::<usize>() % self.executors.read().unwrap().len();
-            let executor = self
-                .executors
-                .read()
-                .unwrap()
-                .get_index(rand_index)
-                .cloned();
-            if let Some(executor) = executor {
-                info!("assigning work {} to executor {}", work.id, executor);
-                work_assignment.insert(work.id.clone(), executor.clone());
-            }
+        // Work of executors which went away is handed out again. Leases are set
+        // with the repository's clock, so they're checked against it too.
+        let now = self.repository.clock().now();
+        let reclaimed = self.repository.reclaim_expired_work(now as i64).await?;
+        if reclaimed > 0 {
+            info!("reclaimed {} work items with expired leases", reclaimed);
+        }
+        let reclaimed = self
+            .repository
+            .reclaim_work_of_dead_executors(EXECUTOR_HEARTBEAT_TIMEOUT)
+            .await?;
+        if reclaimed > 0 {
+            info!("reclaimed {} work items of dead executors", reclaimed);
+        }
+        let executors: Vec<String> = self
+            .live_executors()
+            .await?
+            .into_iter()
+            .map(|executor| executor.id)
+            .collect();
+        if executors.is_empty() {
+            return Ok(());
+        }
+        let work_assignment = self
+            .repository
+            .assign_unallocated_round_robin(&executors)
+            .await?;
+        for (work_id, executor) in &work_assignment {
+            info!("assigned work {} to executor {}", work_id, executor);
         }
-        self.repository.assign_work(work_assignment).await?;
         Ok(())
     }
 
@@ -168,6 +282,7 @@ impl Coordinator {
         &self,
         repository_id: &str,
         content_id: Option<&str>,
+        priority: i32,
     ) -> Result<(), anyhow::Error> {
         let extractor_bindings = self
             .repository
@@ -175,33 +290,93 @@ impl Coordinator {
             .await?
             .extractor_bindings;
         for extractor_binding in &extractor_bindings {
-            let content_list = self
+            let mut content_stream = self
                 .repository
-                .content_with_unapplied_extractor(repository_id, extractor_binding, content_id)
+                .content_with_unapplied_extractor_stream(
+                    repository_id,
+                    extractor_binding,
+                    content_id,
+                    ContentOrder::ById,
+                )
                 .await?;
-            for content in content_list {
+            while let Some(content) = content_stream.try_next().await? {
+                // Each output index gets its own work, so the executor writes every
+                // result to a single index
+                for index_name in &extractor_binding.output_indexes {
+                    info!(
+                        "Creating work for repository: {}, content: {}, extractor: {}, index: {}",
+                        &repository_id, &content.id, &extractor_binding.extractor_name, index_name
+                    );
+                    let work = Work::new(
+                        &content.id,
+                        repository_id,
+                        index_name,
+                        &extractor_binding.extractor_name,
+                        &extractor_binding.input_params,
+                        None,
+                    )
+                    .with_priority(priority);
+                    self.repository.insert_work(&work).await?;
+                }
+                self.repository
+                    .mark_content_as_processed(&content.id, &extractor_binding.id)
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Creates work for the events of a repository which the binding hasn't
+    /// been applied to yet. Events are extracted like text content, the work's
+    /// content id is the event's id.
+    pub async fn create_event_work(
+        &self,
+        repository_id: &str,
+        extractor_binding: &ExtractorBinding,
+    ) -> Result<(), anyhow::Error> {
+        let events = self
+            .repository
+            .events_with_unapplied_extractor(repository_id, extractor_binding, None)
+            .await?;
+        for event in events {
+            for index_name in &extractor_binding.output_indexes {
                 info!(
-                    "Creating work for repository: {}, content: {}, extractor: {}, index: {}",
-                    &repository_id,
-                    &content.id,
-                    &extractor_binding.extractor_name,
-                    &extractor_binding.index_name
+                    "Creating work for repository: {}, event: {}, extractor: {}, index: {}",
+                    &repository_id, &event.id, &extractor_binding.extractor_name, index_name
                 );
                 let work = Work::new(
-                    &content.id,
+                    &event.id,
                     repository_id,
-                    &extractor_binding.index_name,
+                    index_name,
                     &extractor_binding.extractor_name,
                     &extractor_binding.input_params,
                     None,
-                );
+                )
+                .with_event_target();
                 self.repository.insert_work(&work).await?;
-                self.repository
-                    .mark_content_as_processed(&work.content_id, &extractor_binding.id)
-                    .await?;
             }
+            self.repository
+                .mark_event_as_processed(&event.id, &extractor_binding.id)
+                .await?;
         }
+        Ok(())
+    }
 
+    /// Creates work for the bindings of a repository which target its events.
+    async fn create_work_for_events(&self, repository_id: &str) -> Result<(), anyhow::Error> {
+        let extractor_bindings = self
+            .repository
+            .repository_by_name(repository_id)
+            .await?
+            .extractor_bindings;
+        for extractor_binding in extractor_bindings
+            .iter()
+            .filter(|binding| binding.target == ExtractionTarget::Events)
+        {
+            self.create_event_work(repository_id, extractor_binding)
+                .await?;
+        }
         Ok(())
     }
 
@@ -210,24 +385,31 @@ impl Coordinator {
         work_list: Vec<Work>,
         worker_id: &str,
     ) -> Result<(), anyhow::Error> {
+        let mut completed = Vec::new();
         for work in work_list.iter() {
             info!(
                 "updating work status by worker: {}, work id: {}, status: {}",
                 worker_id, &work.id, &work.work_state
             );
             match work.work_state {
-                WorkState::Completed | WorkState::Failed => {
-                    if let Err(err) = self
+                WorkState::Completed => completed.push((work.id.clone(), WorkState::Completed)),
+                // Failed work is tried again until it runs out of attempts
+                WorkState::Failed => {
+                    match self
                         .repository
-                        .update_work_state(&work.id, work.work_state.clone())
+                        .mark_work_failed_with_retry(&work.id, MAX_WORK_ATTEMPTS)
                         .await
                     {
-                        error!("unable to update work state: {}", err.to_string());
+                        Ok(state) => info!("work {} is now {}", &work.id, state),
+                        Err(err) => error!("unable to update work state: {}", err.to_string()),
                     }
                 }
                 _ => {}
             }
         }
+        if let Err(err) = self.repository.update_work_states(completed).await {
+            error!("unable to update work state: {}", err.to_string());
+        }
         Ok(())
     }
 
@@ -248,9 +430,17 @@ impl Coordinator {
     async fn loop_for_work(&self, mut rx: Receiver<CreateWork>) -> Result<(), anyhow::Error> {
         info!("starting work distribution loop");
         loop {
-            if (rx.recv().await).is_none() {
+            let Some(create_work) = rx.recv().await else {
                 info!("no work to process");
                 return Ok(());
+            };
+            // Added events don't record extraction events, so the bindings which
+            // target them are checked whenever work is requested for the repository
+            if let Err(err) = self
+                .create_work_for_events(&create_work.repository_name)
+                .await
+            {
+                error!("unable to create work for events: {}", err.to_string());
             }
             if let Err(err) = self.process_and_distribute_work().await {
                 error!("unable to process and distribute work: {}", err.to_string());
@@ -258,6 +448,35 @@ impl Coordinator {
         }
     }
 
+    /// Queues dry run work for the sample of content, its results are kept
+    /// apart from the real indexes, see `Repository::dry_run_results`.
+    pub async fn create_dry_run_work(
+        &self,
+        repository_id: &str,
+        request: &DryRunRequest,
+    ) -> Result<Vec<String>, anyhow::Error> {
+        let input_params = request.input_params.clone().unwrap_or(json!({}));
+        let mut work_ids = Vec::new();
+        for content_id in &request.content_ids {
+            info!(
+                "Creating dry run work for repository: {}, content: {}, extractor: {}",
+                repository_id, content_id, &request.extractor
+            );
+            let work = Work::new(
+                content_id,
+                repository_id,
+                &request.index_name,
+                &request.extractor,
+                &input_params,
+                None,
+            )
+            .with_dry_run();
+            self.repository.insert_work(&work).await?;
+            work_ids.push(work.id);
+        }
+        Ok(work_ids)
+    }
+
     pub async fn process_and_distribute_work(&self) -> Result<(), anyhow::Error> {
         info!("received work request, processing extraction events");
         self.process_extraction_events().await?;
@@ -276,8 +495,11 @@ pub struct CoordinatorServer {
 impl CoordinatorServer {
     pub async fn new(config: Arc<ServerConfig>) -> Result<Self, anyhow::Error> {
         let addr: SocketAddr = config.coordinator_addr.parse()?;
-        let repository = Arc::new(Repository::new(&config.db_url).await?);
-        let coordinator = Coordinator::new(repository);
+        let repository = Arc::new(Repository::from_config(&config).await?);
+        let coordinator = Coordinator::new(repository.clone());
+        if let Some(retention_secs) = config.extraction_event_retention_secs {
+            tokio::spawn(prune_processed_events(repository, retention_secs));
+        }
         info!("Coordinator listening on: {}", &config.coordinator_addr);
         Ok(Self { addr, coordinator })
     }
@@ -296,6 +518,50 @@ impl CoordinatorServer {
             .route(
                 "/create_work",
                 post(create_work).with_state(self.coordinator.clone()),
+            )
+            .route(
+                "/work/:work_id",
+                get(get_work).with_state(self.coordinator.clone()),
+            )
+            .route(
+                "/work/:work_id",
+                delete(delete_work).with_state(self.coordinator.clone()),
+            )
+            .route(
+                "/work/:work_id/cancel",
+                post(cancel_work).with_state(self.coordinator.clone()),
+            )
+            .route(
+                "/repositories/:repository_name/work",
+                get(list_work).with_state(self.coordinator.clone()),
+            )
+            .route(
+                "/repositories/:repository_name/work_stats",
+                get(work_stats).with_state(self.coordinator.clone()),
+            )
+            .route(
+                "/repositories/:repository_name/retry_failed_work",
+                post(retry_failed_work).with_state(self.coordinator.clone()),
+            )
+            .route(
+                "/repositories/:repository_name/work_queue",
+                get(export_work_queue).with_state(self.coordinator.clone()),
+            )
+            .route(
+                "/work_queue",
+                post(import_work_queue).with_state(self.coordinator.clone()),
+            )
+            .route(
+                "/repositories/:repository_name/migrate_binding_state",
+                post(migrate_binding_state).with_state(self.coordinator.clone()),
+            )
+            .route(
+                "/repositories/:repository_name/dry_run",
+                post(create_dry_run).with_state(self.coordinator.clone()),
+            )
+            .route(
+                "/extractors/:extractor_name/dry_run_results",
+                get(dry_run_results).with_state(self.coordinator.clone()),
             );
         axum::Server::bind(&self.addr)
             .serve(app.into_make_service())
@@ -313,20 +579,30 @@ async fn root() -> &'static str {
     "Indexify Coordinator"
 }
 
+async fn prune_processed_events(repository: Arc<Repository>, retention_secs: u64) {
+    let mut interval =
+        tokio::time::interval(std::time::Duration::from_secs(EVENT_PRUNE_INTERVAL_SECS));
+    loop {
+        interval.tick().await;
+        match repository
+            .prune_processed_events_batched(retention_secs, EVENT_PRUNE_BATCH_SIZE)
+            .await
+        {
+            Ok(0) => {}
+            Ok(deleted) => info!("pruned {} processed extraction events", deleted),
+            Err(err) => error!("unable to prune extraction events: {}", err),
+        }
+    }
+}
+
 #[axum_macros::debug_handler]
 async fn list_executors(
     State(coordinator): State<Arc<Coordinator>>,
 ) -> Result<Json<ListExecutors>, IndexifyAPIError> {
-    // TODO FIX THIS - available extractors needs to be populated.
-    let executors = coordinator.executor_health_checks.read().unwrap().clone();
-    let executors = executors
-        .into_iter()
-        .map(|(id, last_seen)| ExecutorInfo {
-            id,
-            last_seen,
-            available_extractors: vec![],
-        })
-        .collect();
+    let executors = coordinator
+        .live_executors()
+        .await
+        .map_err(|e| IndexifyAPIError::new(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
     Ok(Json(ListExecutors { executors }))
 }
 
@@ -382,6 +658,189 @@ async fn create_work(
     Ok(Json(CreateWorkResponse {}))
 }
 
+#[axum_macros::debug_handler]
+async fn get_work(
+    Path(work_id): Path<String>,
+    State(coordinator): State<Arc<Coordinator>>,
+) -> Result<Json<Work>, IndexifyAPIError> {
+    let work = coordinator
+        .repository
+        .work_by_id(&work_id)
+        .await
+        .map_err(work_api_error)?;
+    Ok(Json(work))
+}
+
+#[axum_macros::debug_handler]
+async fn list_work(
+    Path(repository_name): Path<String>,
+    Query(params): Query<ListWorkParams>,
+    State(coordinator): State<Arc<Coordinator>>,
+) -> Result<Json<ListWorkResponse>, IndexifyAPIError> {
+    let work = coordinator
+        .repository
+        .list_work_by_state(&repository_name, params.state)
+        .await
+        .map_err(work_api_error)?;
+    Ok(Json(ListWorkResponse { work }))
+}
+
+#[axum_macros::debug_handler]
+async fn work_stats(
+    Path(repository_name): Path<String>,
+    State(coordinator): State<Arc<Coordinator>>,
+) -> Result<Json<WorkStatsResponse>, IndexifyAPIError> {
+    let stats = coordinator
+        .repository
+        .work_stats(&repository_name)
+        .await
+        .map_err(work_api_error)?;
+    Ok(Json(WorkStatsResponse { stats }))
+}
+
+#[axum_macros::debug_handler]
+async fn retry_failed_work(
+    Path(repository_name): Path<String>,
+    State(coordinator): State<Arc<Coordinator>>,
+) -> Result<Json<RetryFailedWorkResponse>, IndexifyAPIError> {
+    let requeued = coordinator
+        .repository
+        .retry_all_failed(&repository_name)
+        .await
+        .map_err(work_api_error)?;
+    if requeued > 0 {
+        // Hand the requeued work out again
+        let create_work = CreateWork {
+            repository_name,
+            content: None,
+        };
+        if let Err(err) = coordinator.tx.try_send(create_work) {
+            error!("unable to send create work request: {}", err.to_string());
+        }
+    }
+    Ok(Json(RetryFailedWorkResponse { requeued }))
+}
+
+#[axum_macros::debug_handler]
+async fn export_work_queue(
+    Path(repository_name): Path<String>,
+    State(coordinator): State<Arc<Coordinator>>,
+) -> Result<Json<serde_json::Value>, IndexifyAPIError> {
+    let work_queue = coordinator
+        .repository
+        .export_work_queue(&repository_name)
+        .await
+        .map_err(work_api_error)?;
+    Ok(Json(work_queue))
+}
+
+#[axum_macros::debug_handler]
+async fn import_work_queue(
+    State(coordinator): State<Arc<Coordinator>>,
+    Json(work_queue): Json<serde_json::Value>,
+) -> Result<Json<ImportWorkQueueResponse>, IndexifyAPIError> {
+    coordinator
+        .repository
+        .import_work_queue(work_queue)
+        .await
+        .map_err(work_api_error)?;
+    Ok(Json(ImportWorkQueueResponse {}))
+}
+
+#[axum_macros::debug_handler]
+async fn cancel_work(
+    Path(work_id): Path<String>,
+    State(coordinator): State<Arc<Coordinator>>,
+) -> Result<Json<CancelWorkResponse>, IndexifyAPIError> {
+    coordinator
+        .repository
+        .cancel_work(&work_id)
+        .await
+        .map_err(work_api_error)?;
+    Ok(Json(CancelWorkResponse {}))
+}
+
+#[axum_macros::debug_handler]
+async fn delete_work(
+    Path(work_id): Path<String>,
+    State(coordinator): State<Arc<Coordinator>>,
+) -> Result<Json<DeleteWorkResponse>, IndexifyAPIError> {
+    coordinator
+        .repository
+        .delete_work(&work_id)
+        .await
+        .map_err(work_api_error)?;
+    Ok(Json(DeleteWorkResponse {}))
+}
+
+#[axum_macros::debug_handler]
+async fn migrate_binding_state(
+    Path(repository_name): Path<String>,
+    State(coordinator): State<Arc<Coordinator>>,
+) -> Result<Json<MigrateBindingStateResponse>, IndexifyAPIError> {
+    let migrated = coordinator
+        .repository
+        .migrate_binding_state(&repository_name)
+        .await
+        .map_err(work_api_error)?;
+    if migrated > 0 {
+        // Content whose state wasn't recognized before may still need work
+        let create_work = CreateWork {
+            repository_name,
+            content: None,
+        };
+        if let Err(err) = coordinator.tx.try_send(create_work) {
+            error!("unable to send create work request: {}", err.to_string());
+        }
+    }
+    Ok(Json(MigrateBindingStateResponse { migrated }))
+}
+
+#[axum_macros::debug_handler]
+async fn create_dry_run(
+    Path(repository_name): Path<String>,
+    State(coordinator): State<Arc<Coordinator>>,
+    Json(request): Json<DryRunRequest>,
+) -> Result<Json<DryRunResponse>, IndexifyAPIError> {
+    let work_ids = coordinator
+        .create_dry_run_work(&repository_name, &request)
+        .await
+        .map_err(|e| IndexifyAPIError::new(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let create_work = CreateWork {
+        repository_name,
+        content: None,
+    };
+    if let Err(err) = coordinator.tx.try_send(create_work) {
+        error!("unable to send create work request: {}", err.to_string());
+    }
+    Ok(Json(DryRunResponse { work_ids }))
+}
+
+#[axum_macros::debug_handler]
+async fn dry_run_results(
+    Path(extractor_name): Path<String>,
+    State(coordinator): State<Arc<Coordinator>>,
+) -> Result<Json<DryRunResults>, IndexifyAPIError> {
+    let results = coordinator
+        .repository
+        .dry_run_results(&extractor_name)
+        .await
+        .map_err(work_api_error)?;
+    Ok(Json(results))
+}
+
+fn work_api_error(err: RepositoryError) -> IndexifyAPIError {
+    let status = match err {
+        RepositoryError::WorkNotFound(_) | RepositoryError::RepositoryNotFound(_) => {
+            StatusCode::NOT_FOUND
+        }
+        RepositoryError::WorkAlreadyFinished(_) => StatusCode::CONFLICT,
+        RepositoryError::LogicError(_) => StatusCode::BAD_REQUEST,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    IndexifyAPIError::new(status, err.to_string())
+}
+
 async fn shutdown_signal() {
     let ctrl_c = async {
         signal::ctrl_c()
@@ -423,10 +882,22 @@ mod tests {
     use std::collections::HashMap;
 
     use crate::{
+        clock::MockClock,
         data_repository_manager::DataRepositoryManager,
-        persistence::{DataRepository, Text},
+        persistence::{
+            DataRepository, Event, ExtractionTarget, ExtractorConfig, Repository, Text, WorkState,
+            DEFAULT_WORK_LEASE, EXECUTOR_HEARTBEAT_TIMEOUT, MAX_WORK_ATTEMPTS,
+        },
+    };
+    use std::sync::Arc;
+
+    use axum::{
+        extract::{Path, State},
+        Json,
     };
 
+    use super::{retry_failed_work, Coordinator, DryRunRequest, ExecutorInfo};
+
     #[tokio::test]
     #[tracing_test::traced_test]
     async fn test_create_work() -> Result<(), anyhow::Error> {
@@ -481,4 +952,460 @@ mod tests {
         assert_eq!(work_list.len(), 2);
         Ok(())
     }
+
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn test_work_inherits_content_priority() -> Result<(), anyhow::Error> {
+        let db = test_util::db_utils::create_db().await.unwrap();
+        let repository = Arc::new(Repository::new_with_db(db));
+        repository
+            .upsert_repository(test_util::db_utils::default_test_data_repository())
+            .await?;
+        let coordinator = Coordinator::new(repository.clone());
+        coordinator.process_extraction_events().await?;
+
+        let urgent =
+            Text::from_text(DEFAULT_TEST_REPOSITORY, "urgent", HashMap::new()).with_priority(10);
+        let routine = Text::from_text(DEFAULT_TEST_REPOSITORY, "routine", HashMap::new());
+        repository
+            .add_content(
+                DEFAULT_TEST_REPOSITORY,
+                vec![routine.clone(), urgent.clone()],
+            )
+            .await?;
+        coordinator.process_extraction_events().await?;
+
+        // Unallocated work is listed by descending priority
+        let work_list = repository.unallocated_work().await?;
+        assert_eq!(work_list.len(), 2);
+        assert_eq!(work_list[0].content_id, urgent.id);
+        assert_eq!(work_list[0].priority, 10);
+        assert_eq!(work_list[1].content_id, routine.id);
+        assert_eq!(work_list[1].priority, 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn test_binding_with_several_output_indexes() -> Result<(), anyhow::Error> {
+        let db = test_util::db_utils::create_db().await.unwrap();
+        let repository = Arc::new(Repository::new_with_db(db));
+        let binding = ExtractorBinding::new(
+            DEFAULT_TEST_REPOSITORY,
+            DEFAULT_TEST_EXTRACTOR.into(),
+            "embeddings".into(),
+            vec![],
+            json!({}),
+        )
+        .with_output_indexes(vec!["embeddings".into(), "attributes".into()]);
+        repository
+            .upsert_repository(DataRepository {
+                name: DEFAULT_TEST_REPOSITORY.into(),
+                data_connectors: vec![],
+                metadata: HashMap::new(),
+                extractor_bindings: vec![binding],
+            })
+            .await?;
+        let coordinator = Coordinator::new(repository.clone());
+        coordinator.process_extraction_events().await?;
+
+        let text = Text::from_text(DEFAULT_TEST_REPOSITORY, "hello", HashMap::new());
+        repository
+            .add_content(DEFAULT_TEST_REPOSITORY, vec![text.clone()])
+            .await?;
+        coordinator.process_extraction_events().await?;
+
+        let mut index_names: Vec<String> = repository
+            .unallocated_work()
+            .await?
+            .into_iter()
+            .filter(|work| work.content_id == text.id)
+            .map(|work| work.index_name)
+            .collect();
+        index_names.sort();
+        assert_eq!(vec!["attributes", "embeddings"], index_names);
+
+        // Bindings stored with a single index name still load
+        let legacy: ExtractorBinding = serde_json::from_value(json!({
+            "id": "1",
+            "extractor_name": DEFAULT_TEST_EXTRACTOR,
+            "index_name": "embeddings",
+            "filters": [],
+            "input_params": {},
+        }))?;
+        assert_eq!(vec!["embeddings".to_string()], legacy.output_indexes);
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn test_redelivered_content_requeues_work() -> Result<(), anyhow::Error> {
+        let db = test_util::db_utils::create_db().await.unwrap();
+        let repository = Arc::new(Repository::new_with_db(db));
+        repository
+            .upsert_repository(test_util::db_utils::default_test_data_repository())
+            .await?;
+        let coordinator = Coordinator::new(repository.clone());
+        coordinator.process_extraction_events().await?;
+
+        let text = Text::from_external(
+            DEFAULT_TEST_REPOSITORY,
+            "web",
+            "https://example.com",
+            "hello",
+            HashMap::new(),
+        );
+        repository
+            .add_content(DEFAULT_TEST_REPOSITORY, vec![text.clone()])
+            .await?;
+        coordinator.process_extraction_events().await?;
+        let work_list = repository.unallocated_work().await?;
+        assert_eq!(work_list.len(), 1);
+        let work_id = work_list[0].id.clone();
+        repository
+            .assign_work(HashMap::from([(work_id.clone(), "executor".to_string())]))
+            .await?;
+        repository
+            .update_work_state(&work_id, WorkState::Completed)
+            .await?;
+
+        // The same item delivered again by its source has work already
+        let text = Text::from_external(
+            DEFAULT_TEST_REPOSITORY,
+            "web",
+            "https://example.com",
+            "hello again",
+            HashMap::new(),
+        );
+        repository
+            .add_content(DEFAULT_TEST_REPOSITORY, vec![text])
+            .await?;
+        coordinator.process_extraction_events().await?;
+
+        assert!(repository.unprocessed_extraction_events().await?.is_empty());
+        let work = repository.work_by_id(&work_id).await?;
+        assert_eq!(WorkState::Pending, work.work_state);
+        assert_eq!(None, work.worker_id);
+        assert_eq!(0, work.attempts);
+        assert_eq!(1, repository.unallocated_work().await?.len());
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn test_distribute_work_reclaims_expired_leases() -> Result<(), anyhow::Error> {
+        let db = test_util::db_utils::create_db().await.unwrap();
+        let clock = Arc::new(MockClock::new(1_700_000_000));
+        let repository = Arc::new(Repository::new_with_db(db).with_clock(clock.clone()));
+        repository
+            .upsert_repository(test_util::db_utils::default_test_data_repository())
+            .await?;
+        let coordinator = Coordinator::new(repository.clone());
+        let text = Text::from_text(DEFAULT_TEST_REPOSITORY, "hello", HashMap::new());
+        repository
+            .add_content(DEFAULT_TEST_REPOSITORY, vec![text])
+            .await?;
+        coordinator.process_extraction_events().await?;
+        let work_id = repository.unallocated_work().await?[0].id.clone();
+        repository
+            .assign_work(HashMap::from([(work_id.clone(), "gone".to_string())]))
+            .await?;
+        coordinator
+            .record_executor(ExecutorInfo {
+                id: "alive".into(),
+                ..Default::default()
+            })
+            .await?;
+
+        // The lease still runs by the repository's clock, whatever the system time
+        coordinator.distribute_work().await?;
+        assert_eq!(
+            Some("gone".to_string()),
+            repository.work_by_id(&work_id).await?.worker_id
+        );
+
+        clock.advance(DEFAULT_WORK_LEASE.as_secs() + 1);
+        coordinator
+            .record_executor(ExecutorInfo {
+                id: "alive".into(),
+                ..Default::default()
+            })
+            .await?;
+        coordinator.distribute_work().await?;
+        assert_eq!(
+            Some("alive".to_string()),
+            repository.work_by_id(&work_id).await?.worker_id
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn test_distribute_work_skips_dead_executors() -> Result<(), anyhow::Error> {
+        let db = test_util::db_utils::create_db().await.unwrap();
+        let clock = Arc::new(MockClock::new(1_700_000_000));
+        let repository = Arc::new(Repository::new_with_db(db).with_clock(clock.clone()));
+        repository
+            .upsert_repository(test_util::db_utils::default_test_data_repository())
+            .await?;
+        let coordinator = Coordinator::new(repository.clone());
+        let text = Text::from_text(DEFAULT_TEST_REPOSITORY, "hello", HashMap::new());
+        repository
+            .add_content(DEFAULT_TEST_REPOSITORY, vec![text])
+            .await?;
+        coordinator.process_extraction_events().await?;
+        let work_id = repository.unallocated_work().await?[0].id.clone();
+        let executor = |id: &str| ExecutorInfo {
+            id: id.into(),
+            available_extractors: vec![ExtractorConfig {
+                name: DEFAULT_TEST_EXTRACTOR.into(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        coordinator.record_executor(executor("silent")).await?;
+        repository
+            .assign_work(HashMap::from([(work_id.clone(), "silent".to_string())]))
+            .await?;
+
+        // Only the executor which kept syncing is live, and gets the work of the
+        // one which stopped
+        clock.advance(EXECUTOR_HEARTBEAT_TIMEOUT.as_secs() + 1);
+        coordinator.record_executor(executor("syncing")).await?;
+        let live_executors = coordinator.live_executors().await?;
+        assert_eq!(1, live_executors.len());
+        assert_eq!("syncing", live_executors[0].id);
+        assert_eq!(
+            DEFAULT_TEST_EXTRACTOR,
+            live_executors[0].available_extractors[0].name
+        );
+        coordinator.distribute_work().await?;
+        assert_eq!(
+            Some("syncing".to_string()),
+            repository.work_by_id(&work_id).await?.worker_id
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn test_distribute_work_round_robin() -> Result<(), anyhow::Error> {
+        let db = test_util::db_utils::create_db().await.unwrap();
+        let repository = Arc::new(Repository::new_with_db(db));
+        repository
+            .upsert_repository(test_util::db_utils::default_test_data_repository())
+            .await?;
+        let coordinator = Coordinator::new(repository.clone());
+        let texts = ["one", "two", "three"]
+            .into_iter()
+            .map(|text| Text::from_text(DEFAULT_TEST_REPOSITORY, text, HashMap::new()))
+            .collect();
+        repository
+            .add_content(DEFAULT_TEST_REPOSITORY, texts)
+            .await?;
+        coordinator.process_extraction_events().await?;
+        for id in ["a", "b"] {
+            coordinator
+                .record_executor(ExecutorInfo {
+                    id: id.into(),
+                    ..Default::default()
+                })
+                .await?;
+        }
+
+        coordinator.distribute_work().await?;
+        assert!(repository.unallocated_work().await?.is_empty());
+        let mut work_list = coordinator.get_work_for_worker("a").await?;
+        assert_eq!(2, work_list.len());
+        assert_eq!(1, coordinator.get_work_for_worker("b").await?.len());
+
+        for work in work_list.iter_mut() {
+            work.work_state = WorkState::Completed;
+        }
+        coordinator
+            .update_work_state(work_list.clone(), "a")
+            .await?;
+        assert!(coordinator.get_work_for_worker("a").await?.is_empty());
+        for work in work_list {
+            assert_eq!(
+                WorkState::Completed,
+                repository.work_by_id(&work.id).await?.work_state
+            );
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn test_failed_work_is_retried() -> Result<(), anyhow::Error> {
+        let db = test_util::db_utils::create_db().await.unwrap();
+        let repository = Arc::new(Repository::new_with_db(db));
+        repository
+            .upsert_repository(test_util::db_utils::default_test_data_repository())
+            .await?;
+        let coordinator = Coordinator::new(repository.clone());
+        let text = Text::from_text(DEFAULT_TEST_REPOSITORY, "hello", HashMap::new());
+        repository
+            .add_content(DEFAULT_TEST_REPOSITORY, vec![text])
+            .await?;
+        coordinator.process_extraction_events().await?;
+        coordinator
+            .record_executor(ExecutorInfo {
+                id: "executor".into(),
+                ..Default::default()
+            })
+            .await?;
+
+        for attempt in 1..=MAX_WORK_ATTEMPTS {
+            coordinator.distribute_work().await?;
+            let mut work_list = coordinator.get_work_for_worker("executor").await?;
+            assert_eq!(1, work_list.len());
+            work_list[0].work_state = WorkState::Failed;
+            let work_id = work_list[0].id.clone();
+            coordinator.update_work_state(work_list, "executor").await?;
+            let work = repository.work_by_id(&work_id).await?;
+            assert_eq!(attempt, work.attempts);
+            if attempt < MAX_WORK_ATTEMPTS {
+                assert_eq!(WorkState::Pending, work.work_state);
+                assert_eq!(None, work.worker_id);
+            } else {
+                assert_eq!(WorkState::Failed, work.work_state);
+            }
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn test_exhausted_work_is_retried_manually() -> Result<(), anyhow::Error> {
+        let db = test_util::db_utils::create_db().await.unwrap();
+        let repository = Arc::new(Repository::new_with_db(db));
+        repository
+            .upsert_repository(test_util::db_utils::default_test_data_repository())
+            .await?;
+        let coordinator = Coordinator::new(repository.clone());
+        let text = Text::from_text(DEFAULT_TEST_REPOSITORY, "hello", HashMap::new());
+        repository
+            .add_content(DEFAULT_TEST_REPOSITORY, vec![text])
+            .await?;
+        coordinator.process_extraction_events().await?;
+        coordinator
+            .record_executor(ExecutorInfo {
+                id: "executor".into(),
+                ..Default::default()
+            })
+            .await?;
+
+        let mut work_id = String::new();
+        for _ in 0..MAX_WORK_ATTEMPTS {
+            coordinator.distribute_work().await?;
+            let mut work_list = coordinator.get_work_for_worker("executor").await?;
+            work_list[0].work_state = WorkState::Failed;
+            work_id = work_list[0].id.clone();
+            coordinator.update_work_state(work_list, "executor").await?;
+        }
+        assert_eq!(
+            WorkState::Failed,
+            repository.work_by_id(&work_id).await?.work_state
+        );
+
+        let Json(response) = retry_failed_work(
+            Path(DEFAULT_TEST_REPOSITORY.to_string()),
+            State(coordinator.clone()),
+        )
+        .await
+        .map_err(|_| anyhow::anyhow!("unable to retry failed work"))?;
+        assert_eq!(1, response.requeued);
+        let work = repository.work_by_id(&work_id).await?;
+        assert_eq!(WorkState::Pending, work.work_state);
+        assert_eq!(0, work.attempts);
+        assert_eq!(None, work.worker_id);
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn test_dry_run_work_is_kept_apart() -> Result<(), anyhow::Error> {
+        let db = test_util::db_utils::create_db().await.unwrap();
+        let repository = Arc::new(Repository::new_with_db(db));
+        repository
+            .upsert_repository(test_util::db_utils::default_test_data_repository())
+            .await?;
+        let coordinator = Coordinator::new(repository.clone());
+        let text = Text::from_text(DEFAULT_TEST_REPOSITORY, "hello", HashMap::new());
+        let content_ids = repository
+            .add_content(DEFAULT_TEST_REPOSITORY, vec![text])
+            .await?;
+        coordinator.process_extraction_events().await?;
+
+        let request = DryRunRequest {
+            extractor: DEFAULT_TEST_EXTRACTOR.into(),
+            index_name: "dry_run_index".into(),
+            input_params: None,
+            content_ids: content_ids.clone(),
+        };
+        let work_ids = coordinator
+            .create_dry_run_work(DEFAULT_TEST_REPOSITORY, &request)
+            .await?;
+        assert_eq!(1, work_ids.len());
+        let dry_run_work = repository.work_by_id(&work_ids[0]).await?;
+        assert!(dry_run_work.dry_run);
+        assert_eq!(content_ids[0], dry_run_work.content_id);
+
+        // The real work on the content is still queued
+        let pending = repository
+            .list_work_by_state(DEFAULT_TEST_REPOSITORY, WorkState::Pending)
+            .await?;
+        assert_eq!(2, pending.len());
+        assert_eq!(1, pending.iter().filter(|work| !work.dry_run).count());
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn test_event_work_for_event_bindings() -> Result<(), anyhow::Error> {
+        let db = test_util::db_utils::create_db().await.unwrap();
+        let repository = Arc::new(Repository::new_with_db(db));
+        let mut data_repository = test_util::db_utils::default_test_data_repository();
+        data_repository.extractor_bindings = vec![ExtractorBinding::new(
+            DEFAULT_TEST_REPOSITORY,
+            DEFAULT_TEST_EXTRACTOR.into(),
+            DEFAULT_TEST_EXTRACTOR.into(),
+            vec![],
+            json!({}),
+        )
+        .with_target(ExtractionTarget::Events)];
+        repository.upsert_repository(data_repository).await?;
+        let coordinator = Coordinator::new(repository.clone());
+        let event = Event::new("hello", None, HashMap::new());
+        repository
+            .add_events(DEFAULT_TEST_REPOSITORY, vec![event.clone()])
+            .await?;
+        let text = Text::from_text(DEFAULT_TEST_REPOSITORY, "hello", HashMap::new());
+        repository
+            .add_content(DEFAULT_TEST_REPOSITORY, vec![text])
+            .await?;
+
+        // Only the event gets work
+        coordinator
+            .create_work_for_events(DEFAULT_TEST_REPOSITORY)
+            .await?;
+        coordinator.process_extraction_events().await?;
+        let pending = repository
+            .list_work_by_state(DEFAULT_TEST_REPOSITORY, WorkState::Pending)
+            .await?;
+        assert_eq!(1, pending.len());
+        assert_eq!(event.id, pending[0].content_id);
+        assert_eq!(ExtractionTarget::Events, pending[0].target);
+
+        // The event isn't handed out again
+        coordinator
+            .create_work_for_events(DEFAULT_TEST_REPOSITORY)
+            .await?;
+        let pending = repository
+            .list_work_by_state(DEFAULT_TEST_REPOSITORY, WorkState::Pending)
+            .await?;
+        assert_eq!(1, pending.len());
+        Ok(())
+    }
 }
@@ -0,0 +1,207 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::UNIX_EPOCH;
+
+use glob::Pattern;
+use serde_json::json;
+use tracing::{info, warn};
+use walkdir::WalkDir;
+
+use super::VersionCursor;
+use crate::persistence::{ContentType, Repository, RepositoryError, Text};
+
+/// Ingests the files under a local directory as content, optionally only those
+/// whose path relative to the directory matches a glob. Each file is identified
+/// by its relative path, so a file which changed replaces the content it was
+/// ingested as before. A file is taken to have changed when its size or
+/// modification time did.
+pub struct LocalFsConnector {
+    path: PathBuf,
+    glob: Option<Pattern>,
+}
+
+impl LocalFsConnector {
+    pub fn new(path: &str, glob: Option<&str>) -> Result<Self, RepositoryError> {
+        let glob = glob
+            .map(Pattern::new)
+            .transpose()
+            .map_err(|e| RepositoryError::LogicError(format!("invalid glob: {}", e)))?;
+        Ok(Self {
+            path: path.into(),
+            glob,
+        })
+    }
+
+    fn source(&self) -> String {
+        format!("file://{}", self.path.display())
+    }
+
+    /// Identifies the connector's cursor.
+    pub fn id(&self) -> String {
+        match &self.glob {
+            Some(glob) => format!("{}/{}", self.source(), glob),
+            None => self.source(),
+        }
+    }
+
+    /// Adds the matching files which are new or changed since the last sync to a
+    /// repository, and returns the ids of their content in the order of their
+    /// paths. Files which aren't text are skipped.
+    pub async fn sync(
+        &self,
+        repository: &Repository,
+        repository_name: &str,
+    ) -> Result<Vec<String>, RepositoryError> {
+        if !self.path.is_dir() {
+            return Err(RepositoryError::LogicError(format!(
+                "{} is not a directory",
+                self.path.display()
+            )));
+        }
+        let cursor = VersionCursor::load(repository, repository_name, &self.id()).await?;
+        let mut next_cursor = VersionCursor::default();
+        let source = self.source();
+        let mut texts = Vec::new();
+        for entry in WalkDir::new(&self.path).sort_by_file_name() {
+            let entry = entry.map_err(|e| RepositoryError::LogicError(e.to_string()))?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let relative_path = entry
+                .path()
+                .strip_prefix(&self.path)
+                .unwrap_or(entry.path())
+                .to_string_lossy()
+                .to_string();
+            if let Some(glob) = &self.glob {
+                if !glob.matches(&relative_path) {
+                    continue;
+                }
+            }
+            let metadata = entry
+                .metadata()
+                .map_err(|e| RepositoryError::LogicError(e.to_string()))?;
+            let modified = metadata
+                .modified()
+                .ok()
+                .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+                .unwrap_or_default();
+            let version = format!("{}-{}", modified.as_nanos(), metadata.len());
+            let is_current = cursor.is_current(&relative_path, &version);
+            next_cursor.versions.insert(relative_path.clone(), version);
+            if is_current {
+                continue;
+            }
+            let bytes = fs::read(entry.path()).map_err(|e| {
+                RepositoryError::LogicError(format!(
+                    "unable to read {}: {}",
+                    entry.path().display(),
+                    e
+                ))
+            })?;
+            let content_type = ContentType::detect_from_bytes(&bytes);
+            match String::from_utf8(bytes) {
+                Ok(text) => texts.push(
+                    Text::from_external(
+                        repository_name,
+                        &source,
+                        &relative_path,
+                        &text,
+                        HashMap::from([(
+                            "path".to_string(),
+                            json!(entry.path().to_string_lossy()),
+                        )]),
+                    )
+                    .with_content_type(content_type),
+                ),
+                Err(_) => warn!("skipping file {} which isn't text", entry.path().display()),
+            }
+        }
+        let ids = texts.iter().map(|text| text.id.clone()).collect();
+        if !texts.is_empty() {
+            info!(
+                "adding {} files of {} to repository {}",
+                texts.len(),
+                self.path.display(),
+                repository_name
+            );
+            repository.add_content(repository_name, texts).await?;
+        }
+        next_cursor
+            .store(repository, repository_name, &self.id())
+            .await?;
+        Ok(ids)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::fs;
+
+    use nanoid::nanoid;
+
+    use super::LocalFsConnector;
+    use crate::persistence::{Repository, RepositoryError, Text};
+    use crate::test_util::db_utils::create_db;
+
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn test_sync_local_fs() {
+        let db = create_db().await.unwrap();
+        let repository = Repository::new_with_db(db);
+        let dir = std::env::temp_dir().join(format!("indexify-fs-{}", nanoid!()));
+        fs::create_dir_all(dir.join("notes")).unwrap();
+        fs::write(dir.join("a.txt"), "hello").unwrap();
+        fs::write(dir.join("notes").join("b.txt"), "world").unwrap();
+        fs::write(dir.join("image.png"), [0x89, b'P', b'N', b'G']).unwrap();
+        let dir_path = dir.to_string_lossy().to_string();
+
+        let connector = LocalFsConnector::new(&dir_path, Some("*.txt")).unwrap();
+        assert_eq!(format!("file://{}/*.txt", dir_path), connector.id());
+        let ids = connector.sync(&repository, "test").await.unwrap();
+        let source = format!("file://{}", dir_path);
+        let b_path = format!("notes{}b.txt", std::path::MAIN_SEPARATOR);
+        let expected: Vec<String> = [("a.txt", "hello"), (b_path.as_str(), "world")]
+            .into_iter()
+            .map(|(path, text)| Text::from_external("test", &source, path, text, HashMap::new()).id)
+            .collect();
+        assert_eq!(expected, ids);
+        let content = repository.content_from_repo(&ids[1], "test").await.unwrap();
+        assert_eq!("world", content.content);
+        assert_eq!(
+            Some(&serde_json::json!(dir.join("notes").join("b.txt"))),
+            content.metadata.get("path")
+        );
+        assert_eq!(2, repository.count_content("test").await.unwrap());
+
+        // Syncing again only reads the files which changed, and replaces their
+        // content
+        assert!(connector
+            .sync(&repository, "test")
+            .await
+            .unwrap()
+            .is_empty());
+        fs::write(dir.join("a.txt"), "hello again").unwrap();
+        assert_eq!(
+            vec![ids[0].clone()],
+            connector.sync(&repository, "test").await.unwrap()
+        );
+        assert_eq!(
+            "hello again",
+            repository
+                .content_from_repo(&ids[0], "test")
+                .await
+                .unwrap()
+                .content
+        );
+        fs::remove_dir_all(&dir).unwrap();
+
+        let missing = LocalFsConnector::new(&dir_path, None).unwrap();
+        assert!(matches!(
+            missing.sync(&repository, "test").await,
+            Err(RepositoryError::LogicError(_))
+        ));
+    }
+}
@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tracing::warn;
+
+use crate::persistence::{Repository, RepositoryError, SourceType};
+
+use self::{local_fs::LocalFsConnector, s3::S3Connector, web_page::WebPageConnector};
+
+pub mod local_fs;
+pub mod s3;
+pub mod web_page;
+
+/// Where a connector got to: the version of each item it saw in its last sync,
+/// keyed by the item's id in its source. Items whose version hasn't changed
+/// since are skipped by the next sync.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VersionCursor {
+    pub versions: HashMap<String, String>,
+}
+
+impl VersionCursor {
+    /// The cursor a connector of a repository recorded, or an empty one, so that
+    /// everything is synced, when there is none it can read.
+    pub async fn load(
+        repository: &Repository,
+        repository_name: &str,
+        connector_id: &str,
+    ) -> Result<Self, RepositoryError> {
+        let cursor = repository
+            .get_connector_cursor(repository_name, connector_id)
+            .await?;
+        Ok(match cursor.map(serde_json::from_value) {
+            Some(Ok(cursor)) => cursor,
+            Some(Err(err)) => {
+                warn!("ignoring unreadable cursor of {}: {}", connector_id, err);
+                Self::default()
+            }
+            None => Self::default(),
+        })
+    }
+
+    pub async fn store(
+        &self,
+        repository: &Repository,
+        repository_name: &str,
+        connector_id: &str,
+    ) -> Result<(), RepositoryError> {
+        repository
+            .set_connector_cursor(repository_name, connector_id, json!(self))
+            .await
+    }
+
+    pub fn is_current(&self, id: &str, version: &str) -> bool {
+        self.versions.get(id).map(String::as_str) == Some(version)
+    }
+}
+
+/// Syncs the connector reading from `source` into a repository, returning the
+/// ids of the content it added.
+pub async fn sync(
+    repository: &Repository,
+    repository_name: &str,
+    source: &SourceType,
+) -> Result<Vec<String>, RepositoryError> {
+    match source {
+        SourceType::S3 {
+            bucket,
+            prefix,
+            region,
+        } => {
+            S3Connector::new(bucket, prefix.clone(), region)?
+                .sync(repository, repository_name)
+                .await
+        }
+        SourceType::LocalFs { path, glob } => {
+            LocalFsConnector::new(path, glob.as_deref())?
+                .sync(repository, repository_name)
+                .await
+        }
+        SourceType::WebPage {
+            url,
+            depth,
+            max_pages,
+        } => {
+            let mut connector = WebPageConnector::new(url, *depth);
+            if let Some(max_pages) = max_pages {
+                connector = connector.with_max_pages(*max_pages);
+            }
+            connector.sync(repository, repository_name).await
+        }
+        SourceType::GoogleContact { .. } | SourceType::Gmail { .. } => Err(
+            RepositoryError::LogicError(format!("syncing {:?} is not supported", source)),
+        ),
+    }
+}
@@ -0,0 +1,222 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use futures::TryStreamExt;
+use object_store::{aws::AmazonS3Builder, path::Path, ObjectMeta, ObjectStore, ObjectStoreExt};
+use serde_json::json;
+use tracing::{info, warn};
+
+use super::VersionCursor;
+use crate::persistence::{ContentType, Repository, RepositoryError, Text};
+
+/// Ingests the objects of an S3 bucket, optionally under a prefix, as content.
+/// Each object is identified by its key, so an object which changed replaces the
+/// content it was ingested as before.
+pub struct S3Connector {
+    store: Arc<dyn ObjectStore>,
+    bucket: String,
+    prefix: Option<String>,
+}
+
+impl S3Connector {
+    /// Connects to a bucket with the credentials given in the `AWS_*` environment
+    /// variables.
+    pub fn new(
+        bucket: &str,
+        prefix: Option<String>,
+        region: &str,
+    ) -> Result<Self, RepositoryError> {
+        let store = AmazonS3Builder::from_env()
+            .with_bucket_name(bucket)
+            .with_region(region)
+            .build()
+            .map_err(|e| RepositoryError::LogicError(e.to_string()))?;
+        Ok(Self::with_store(Arc::new(store), bucket, prefix))
+    }
+
+    pub fn with_store(store: Arc<dyn ObjectStore>, bucket: &str, prefix: Option<String>) -> Self {
+        Self {
+            store,
+            bucket: bucket.into(),
+            prefix,
+        }
+    }
+
+    fn source(&self) -> String {
+        format!("s3://{}", self.bucket)
+    }
+
+    /// Identifies the connector's cursor.
+    pub fn id(&self) -> String {
+        format!(
+            "{}/{}",
+            self.source(),
+            self.prefix.as_deref().unwrap_or_default()
+        )
+    }
+
+    /// Adds the objects which are new or changed since the last sync to a
+    /// repository, and returns the ids of their content. Objects which aren't
+    /// text are skipped.
+    pub async fn sync(
+        &self,
+        repository: &Repository,
+        repository_name: &str,
+    ) -> Result<Vec<String>, RepositoryError> {
+        let cursor = VersionCursor::load(repository, repository_name, &self.id()).await?;
+        let prefix = self.prefix.as_deref().map(Path::from);
+        let objects: Vec<ObjectMeta> = self
+            .store
+            .list(prefix.as_ref())
+            .try_collect()
+            .await
+            .map_err(|e| {
+                RepositoryError::LogicError(format!("unable to list bucket {}: {}", self.bucket, e))
+            })?;
+
+        let source = self.source();
+        let mut next_cursor = VersionCursor::default();
+        let mut texts = Vec::new();
+        for object in objects {
+            let key = object.location.to_string();
+            // Not every store gives objects an ETag
+            let version = object
+                .e_tag
+                .clone()
+                .unwrap_or_else(|| object.last_modified.timestamp_millis().to_string());
+            if !cursor.is_current(&key, &version) {
+                let bytes = self.fetch(&object.location).await.map_err(|e| {
+                    RepositoryError::LogicError(format!(
+                        "unable to read object {} of bucket {}: {}",
+                        key, self.bucket, e
+                    ))
+                })?;
+                let content_type = ContentType::detect_from_bytes(&bytes);
+                match String::from_utf8(bytes) {
+                    Ok(text) => texts.push(
+                        Text::from_external(
+                            repository_name,
+                            &source,
+                            &key,
+                            &text,
+                            HashMap::from([("key".to_string(), json!(key))]),
+                        )
+                        .with_content_type(content_type),
+                    ),
+                    Err(_) => warn!(
+                        "skipping object {} of bucket {} which isn't text",
+                        key, self.bucket
+                    ),
+                }
+            }
+            next_cursor.versions.insert(key, version);
+        }
+        let ids = texts.iter().map(|text| text.id.clone()).collect();
+        if !texts.is_empty() {
+            info!(
+                "adding {} objects of bucket {} to repository {}",
+                texts.len(),
+                self.bucket,
+                repository_name
+            );
+            repository.add_content(repository_name, texts).await?;
+        }
+        next_cursor
+            .store(repository, repository_name, &self.id())
+            .await?;
+        Ok(ids)
+    }
+
+    async fn fetch(&self, location: &Path) -> object_store::Result<Vec<u8>> {
+        Ok(self.store.get(location).await?.bytes().await?.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    use object_store::{memory::InMemory, path::Path, ObjectStoreExt};
+
+    use super::S3Connector;
+    use crate::data_connectors::VersionCursor;
+    use crate::persistence::{Repository, Text};
+    use crate::test_util::db_utils::create_db;
+
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn test_sync_s3() {
+        let db = create_db().await.unwrap();
+        let repository = Repository::new_with_db(db);
+        let store = Arc::new(InMemory::new());
+        store
+            .put(&Path::from("docs/a.txt"), "hello".into())
+            .await
+            .unwrap();
+        store
+            .put(&Path::from("docs/b.txt"), "world".into())
+            .await
+            .unwrap();
+        store
+            .put(&Path::from("other/c.txt"), "elsewhere".into())
+            .await
+            .unwrap();
+        let connector = S3Connector::with_store(store.clone(), "bucket", Some("docs".into()));
+        assert_eq!("s3://bucket/docs", connector.id());
+        let id = |key| Text::from_external("test", "s3://bucket", key, "", HashMap::new()).id;
+
+        let ids = connector.sync(&repository, "test").await.unwrap();
+        assert_eq!(vec![id("docs/a.txt"), id("docs/b.txt")], ids);
+        for (key, text) in [("docs/a.txt", "hello"), ("docs/b.txt", "world")] {
+            let content = repository
+                .content_from_repo(&id(key), "test")
+                .await
+                .unwrap();
+            assert_eq!(text, content.content);
+            assert_eq!(Some(&serde_json::json!(key)), content.metadata.get("key"));
+        }
+        assert_eq!(2, repository.count_content("test").await.unwrap());
+        let cursor = VersionCursor::load(&repository, "test", &connector.id())
+            .await
+            .unwrap();
+        let mut keys: Vec<&String> = cursor.versions.keys().collect();
+        keys.sort();
+        assert_eq!(vec!["docs/a.txt", "docs/b.txt"], keys);
+
+        // Only the object which changed is read again
+        store
+            .put(&Path::from("docs/b.txt"), "world, again".into())
+            .await
+            .unwrap();
+        assert_eq!(
+            vec![id("docs/b.txt")],
+            connector.sync(&repository, "test").await.unwrap()
+        );
+        let next_cursor = VersionCursor::load(&repository, "test", &connector.id())
+            .await
+            .unwrap();
+        assert_eq!(
+            cursor.versions["docs/a.txt"],
+            next_cursor.versions["docs/a.txt"]
+        );
+        assert_ne!(
+            cursor.versions["docs/b.txt"],
+            next_cursor.versions["docs/b.txt"]
+        );
+        assert_eq!(
+            "world, again",
+            repository
+                .content_from_repo(&id("docs/b.txt"), "test")
+                .await
+                .unwrap()
+                .content
+        );
+        assert_eq!(2, repository.count_content("test").await.unwrap());
+        assert!(connector
+            .sync(&repository, "test")
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,275 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use reqwest::Url;
+use scraper::{Html, Selector};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use tracing::{info, warn};
+
+use super::VersionCursor;
+use crate::persistence::{Repository, RepositoryError, Text};
+
+/// The most pages a crawl reads unless told otherwise.
+const DEFAULT_MAX_PAGES: usize = 100;
+
+/// Ingests the text of a web page, and of the pages it links to up to `depth`
+/// links away, as content. Only links to the same host as the first page are
+/// followed. Each page is identified by its URL, so a page which changed
+/// replaces the content it was ingested as before. A page is taken to have
+/// changed when its visible text did.
+pub struct WebPageConnector {
+    url: String,
+    depth: u8,
+    max_pages: usize,
+    client: reqwest::Client,
+}
+
+impl WebPageConnector {
+    pub fn new(url: &str, depth: u8) -> Self {
+        Self {
+            url: url.into(),
+            depth,
+            max_pages: DEFAULT_MAX_PAGES,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    pub fn with_max_pages(mut self, max_pages: usize) -> Self {
+        self.max_pages = max_pages;
+        self
+    }
+
+    /// Identifies the connector's cursor.
+    pub fn id(&self) -> String {
+        self.url.clone()
+    }
+
+    /// Crawls the pages and adds the text of those which are new or changed since
+    /// the last sync to a repository, returning the ids of their content in the
+    /// order the pages were read. Unchanged pages are still read for their links.
+    /// Failing to read the first page is an error, while pages linked to which
+    /// can't be read are skipped.
+    pub async fn sync(
+        &self,
+        repository: &Repository,
+        repository_name: &str,
+    ) -> Result<Vec<String>, RepositoryError> {
+        let start = Url::parse(&self.url)
+            .map_err(|e| RepositoryError::LogicError(format!("invalid url {}: {}", self.url, e)))?;
+        let cursor = VersionCursor::load(repository, repository_name, &self.id()).await?;
+        let mut next_cursor = VersionCursor::default();
+        let mut seen = HashSet::from([start.clone()]);
+        let mut pending = VecDeque::from([(start.clone(), 0)]);
+        let mut texts = Vec::new();
+        while let Some((url, depth)) = pending.pop_front() {
+            if next_cursor.versions.len() >= self.max_pages {
+                warn!(
+                    "stopping the crawl of {} after {} pages",
+                    self.url, self.max_pages
+                );
+                break;
+            }
+            let body = match self.fetch(&url).await {
+                Ok(body) => body,
+                Err(err) if url == start => {
+                    return Err(RepositoryError::LogicError(format!(
+                        "unable to fetch {}: {}",
+                        url, err
+                    )))
+                }
+                Err(err) => {
+                    warn!("skipping page {}: {}", url, err);
+                    continue;
+                }
+            };
+            let (text, links) = parse_page(&url, &body);
+            if depth < self.depth {
+                for link in links {
+                    if link.host_str() == start.host_str() && seen.insert(link.clone()) {
+                        pending.push_back((link, depth + 1));
+                    }
+                }
+            }
+            let version = format!("{:x}", Sha256::digest(text.as_bytes()));
+            let is_current = cursor.is_current(url.as_str(), &version);
+            next_cursor.versions.insert(url.to_string(), version);
+            if is_current {
+                continue;
+            }
+            texts.push(Text::from_external(
+                repository_name,
+                "web",
+                url.as_str(),
+                &text,
+                HashMap::from([("url".to_string(), json!(url.as_str()))]),
+            ));
+        }
+        let ids = texts.iter().map(|text| text.id.clone()).collect();
+        if !texts.is_empty() {
+            info!(
+                "adding {} pages of {} to repository {}",
+                texts.len(),
+                self.url,
+                repository_name
+            );
+            repository.add_content(repository_name, texts).await?;
+        }
+        next_cursor
+            .store(repository, repository_name, &self.id())
+            .await?;
+        Ok(ids)
+    }
+
+    async fn fetch(&self, url: &Url) -> Result<String, reqwest::Error> {
+        self.client
+            .get(url.clone())
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await
+    }
+}
+
+/// The visible text of a page, with runs of whitespace collapsed, and the http(s)
+/// pages it links to.
+fn parse_page(url: &Url, body: &str) -> (String, Vec<Url>) {
+    let document = Html::parse_document(body);
+    let mut words = Vec::new();
+    for node in document.root_element().descendants() {
+        if let Some(text) = node.value().as_text() {
+            let hidden = node.ancestors().any(|ancestor| {
+                ancestor.value().as_element().is_some_and(|element| {
+                    matches!(element.name(), "head" | "script" | "style" | "noscript")
+                })
+            });
+            if !hidden {
+                words.extend(text.split_whitespace());
+            }
+        }
+    }
+    let anchors = Selector::parse("a[href]").unwrap();
+    let links = document
+        .select(&anchors)
+        .filter_map(|anchor| url.join(anchor.attr("href")?).ok())
+        .filter(|link| matches!(link.scheme(), "http" | "https"))
+        .map(|mut link| {
+            link.set_fragment(None);
+            link
+        })
+        .collect();
+    (words.join(" "), links)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+
+    use axum::{response::Html, routing::get, Router};
+
+    use super::WebPageConnector;
+    use crate::persistence::{Repository, Text};
+    use crate::test_util::db_utils::create_db;
+
+    // Serves two pages which link to each other, and returns the url of the first.
+    // The text of the second page can be changed through `about`.
+    fn serve_pages(about: Arc<Mutex<String>>) -> String {
+        let app = Router::new()
+            .route(
+                "/",
+                get(|| async {
+                    Html(
+                        "<html><head><title>Home</title><script>var x;</script></head>\
+                         <body><h1>Welcome</h1><p>See the <a href=\"/about#team\">about</a> \
+                         page, or <a href=\"http://example.invalid/\">elsewhere</a>.</p>\
+                         </body></html>",
+                    )
+                }),
+            )
+            .route(
+                "/about",
+                get(move || async move {
+                    Html(format!(
+                        "<p>{} <a href=\"/\">Home</a></p>",
+                        about.lock().unwrap()
+                    ))
+                }),
+            );
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::Server::from_tcp(listener)
+                .unwrap()
+                .serve(app.into_make_service())
+                .await
+                .unwrap();
+        });
+        format!("http://{}/", addr)
+    }
+
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn test_sync_web_page() {
+        let db = create_db().await.unwrap();
+        let repository = Repository::new_with_db(db);
+        let about_text = Arc::new(Mutex::new("About us.".to_string()));
+        let url = serve_pages(about_text.clone());
+        let about_url = format!("{}about", url);
+
+        let ids = WebPageConnector::new(&url, 1)
+            .sync(&repository, "test")
+            .await
+            .unwrap();
+        let expected: Vec<String> = [&url, &about_url]
+            .into_iter()
+            .map(|url| Text::from_external("test", "web", url, "", HashMap::new()).id)
+            .collect();
+        assert_eq!(expected, ids);
+        let home = repository.content_from_repo(&ids[0], "test").await.unwrap();
+        assert_eq!("Welcome See the about page, or elsewhere .", home.content);
+        assert_eq!(Some(&serde_json::json!(url)), home.metadata.get("url"));
+        let about = repository.content_from_repo(&ids[1], "test").await.unwrap();
+        assert_eq!("About us. Home", about.content);
+        assert_eq!(
+            Some(&serde_json::json!(about_url)),
+            about.metadata.get("url")
+        );
+
+        // Unchanged pages aren't added again, changed ones are
+        let connector = WebPageConnector::new(&url, 1);
+        assert!(connector
+            .sync(&repository, "test")
+            .await
+            .unwrap()
+            .is_empty());
+        *about_text.lock().unwrap() = "About them.".to_string();
+        assert_eq!(
+            vec![ids[1].clone()],
+            connector.sync(&repository, "test").await.unwrap()
+        );
+        let about = repository.content_from_repo(&ids[1], "test").await.unwrap();
+        assert_eq!("About them. Home", about.content);
+        assert_eq!(2, repository.count_content("test").await.unwrap());
+
+        // Links aren't followed past the depth, nor past the page limit
+        let home_id =
+            |repository: &str| Text::from_external(repository, "web", &url, "", HashMap::new()).id;
+        assert_eq!(
+            vec![home_id("shallow")],
+            WebPageConnector::new(&url, 0)
+                .sync(&repository, "shallow")
+                .await
+                .unwrap()
+        );
+        assert_eq!(
+            vec![home_id("limited")],
+            WebPageConnector::new(&url, 5)
+                .with_max_pages(1)
+                .sync(&repository, "limited")
+                .await
+                .unwrap()
+        );
+    }
+}
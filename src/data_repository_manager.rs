@@ -8,10 +8,14 @@ pub const DEFAULT_REPOSITORY_NAME: &str = "default";
 
 use crate::{
     attribute_index::AttributeIndexManager,
+    data_connectors,
+    entity::index,
     index::IndexError,
     persistence::{
-        DataRepository, Event, ExtractedAttributes, ExtractorBinding, ExtractorConfig,
-        ExtractorType, Repository, RepositoryError, Text,
+        ConfigWarning, Content, ContentOrder, DataRepository, DeletionReport, Event, EventSink,
+        Executor, ExtractedAttributes, ExtractorBinding, ExtractorConfig, ExtractorFilter,
+        ExtractorType, Pagination, ReconcileReport, Repository, RepositoryError, SizeEstimate,
+        Text, EXECUTOR_HEARTBEAT_TIMEOUT,
     },
     vector_index::{ScoredText, VectorIndexManager},
     ServerConfig,
@@ -90,6 +94,17 @@ impl DataRepositoryManager {
             .map_err(DataRepositoryError::Persistence)
     }
 
+    pub async fn find_repositories_by_metadata(
+        &self,
+        key: &str,
+        value: serde_json::Value,
+    ) -> Result<Vec<DataRepository>, DataRepositoryError> {
+        self.repository
+            .find_repositories_by_metadata(key, value)
+            .await
+            .map_err(DataRepositoryError::Persistence)
+    }
+
     async fn create_index(
         &self,
         repository: &str,
@@ -104,10 +119,12 @@ impl DataRepositoryManager {
             distance: _,
         } = extractor.extractor_type.clone()
         {
-            self.vector_index_manager
-                .create_index(repository, &extractor_binding.index_name, extractor)
-                .await
-                .map_err(|e| DataRepositoryError::IndexCreation(e.to_string()))?;
+            for index_name in &extractor_binding.output_indexes {
+                self.vector_index_manager
+                    .create_index(repository, index_name, extractor.clone())
+                    .await
+                    .map_err(|e| DataRepositoryError::IndexCreation(e.to_string()))?;
+            }
         }
         Ok(())
     }
@@ -135,15 +152,272 @@ impl DataRepositoryManager {
             .map_err(DataRepositoryError::Persistence)
     }
 
+    pub async fn list_repositories_including_deleted(
+        &self,
+    ) -> Result<Vec<DataRepository>, DataRepositoryError> {
+        self.repository
+            .repositories_including_deleted()
+            .await
+            .map_err(DataRepositoryError::Persistence)
+    }
+
+    pub async fn update_repository_metadata(
+        &self,
+        repository: &str,
+        metadata: HashMap<String, serde_json::Value>,
+    ) -> Result<(), DataRepositoryError> {
+        self.repository
+            .update_repository_metadata(repository, metadata)
+            .await
+            .map_err(DataRepositoryError::Persistence)
+    }
+
+    pub async fn soft_delete_repository(
+        &self,
+        repository: &str,
+    ) -> Result<(), DataRepositoryError> {
+        self.repository
+            .soft_delete_repository(repository)
+            .await
+            .map_err(DataRepositoryError::Persistence)
+    }
+
+    pub async fn restore_repository(&self, repository: &str) -> Result<(), DataRepositoryError> {
+        self.repository
+            .restore_repository(repository)
+            .await
+            .map_err(DataRepositoryError::Persistence)
+    }
+
+    pub async fn delete_repository(
+        &self,
+        repository: &str,
+    ) -> Result<DeletionReport, DataRepositoryError> {
+        self.repository
+            .delete_repository(repository, self.vector_index_manager.vector_db())
+            .await
+            .map_err(DataRepositoryError::Persistence)
+    }
+
+    pub async fn add_binary_content(
+        &self,
+        repo_name: &str,
+        bytes: Vec<u8>,
+    ) -> Result<String, DataRepositoryError> {
+        let _ = self.repository.repository_by_name(repo_name).await?;
+        self.repository
+            .add_binary_content(repo_name, bytes, HashMap::new())
+            .await
+            .map_err(DataRepositoryError::Persistence)
+    }
+
+    pub async fn add_content_auto(
+        &self,
+        repo_name: &str,
+        bytes: Vec<u8>,
+    ) -> Result<String, DataRepositoryError> {
+        let _ = self.repository.repository_by_name(repo_name).await?;
+        self.repository
+            .add_content_auto(repo_name, bytes, HashMap::new())
+            .await
+            .map_err(DataRepositoryError::Persistence)
+    }
+
+    pub async fn binary_content(
+        &self,
+        repository: &str,
+        content_id: &str,
+    ) -> Result<Content<Vec<u8>>, DataRepositoryError> {
+        self.repository
+            .binary_content_from_repo(content_id, repository)
+            .await
+            .map_err(DataRepositoryError::Persistence)
+    }
+
+    pub async fn delete_content(
+        &self,
+        repository: &str,
+        content_id: &str,
+    ) -> Result<DeletionReport, DataRepositoryError> {
+        self.repository
+            .delete_content(
+                repository,
+                content_id,
+                self.vector_index_manager.vector_db(),
+            )
+            .await
+            .map_err(DataRepositoryError::Persistence)
+    }
+
+    pub async fn list_indexes(
+        &self,
+        repository: &str,
+    ) -> Result<Vec<index::Model>, DataRepositoryError> {
+        self.repository
+            .list_indexes(repository)
+            .await
+            .map_err(DataRepositoryError::Persistence)
+    }
+
+    pub async fn estimate_index_size(
+        &self,
+        repository: &str,
+        index_name: &str,
+    ) -> Result<SizeEstimate, DataRepositoryError> {
+        self.repository
+            .estimate_index_size(repository, index_name)
+            .await
+            .map_err(DataRepositoryError::Persistence)
+    }
+
+    pub async fn delete_index(
+        &self,
+        repository: &str,
+        index_name: &str,
+    ) -> Result<DeletionReport, DataRepositoryError> {
+        self.repository
+            .delete_index(
+                repository,
+                index_name,
+                self.vector_index_manager.vector_db(),
+            )
+            .await
+            .map_err(DataRepositoryError::Persistence)
+    }
+
+    pub async fn reconcile_index(
+        &self,
+        repository: &str,
+        index_name: &str,
+        repair: bool,
+    ) -> Result<ReconcileReport, DataRepositoryError> {
+        self.repository
+            .reconcile_index(
+                repository,
+                index_name,
+                self.vector_index_manager.vector_db(),
+                repair,
+            )
+            .await
+            .map_err(DataRepositoryError::Persistence)
+    }
+
+    pub async fn validate_repository(
+        &self,
+        repository: &str,
+    ) -> Result<Vec<ConfigWarning>, DataRepositoryError> {
+        self.repository
+            .validate_repository(repository, self.vector_index_manager.vector_db())
+            .await
+            .map_err(DataRepositoryError::Persistence)
+    }
+
+    pub async fn binding_lag(
+        &self,
+        repository: &str,
+    ) -> Result<HashMap<String, Option<u64>>, DataRepositoryError> {
+        self.repository
+            .binding_lag(repository)
+            .await
+            .map_err(DataRepositoryError::Persistence)
+    }
+
+    pub async fn count_content(&self, repository: &str) -> Result<u64, DataRepositoryError> {
+        self.repository
+            .count_content(repository)
+            .await
+            .map_err(DataRepositoryError::Persistence)
+    }
+
+    pub async fn count_content_by_type(
+        &self,
+        repository: &str,
+    ) -> Result<HashMap<String, u64>, DataRepositoryError> {
+        self.repository
+            .count_content_by_type(repository)
+            .await
+            .map_err(DataRepositoryError::Persistence)
+    }
+
+    pub async fn content_counts_by_source(
+        &self,
+        repository: &str,
+    ) -> Result<HashMap<String, u64>, DataRepositoryError> {
+        self.repository
+            .content_counts_by_source(repository)
+            .await
+            .map_err(DataRepositoryError::Persistence)
+    }
+
+    pub async fn extraction_progress(&self, repository: &str) -> Result<f32, DataRepositoryError> {
+        self.repository
+            .extraction_progress(repository)
+            .await
+            .map_err(DataRepositoryError::Persistence)
+    }
+
+    pub async fn remove_extractor_binding(
+        &self,
+        repository: &str,
+        binding_id: &str,
+        clear_state: bool,
+    ) -> Result<(), DataRepositoryError> {
+        info!(
+            "removing extractor binding: repository: {}, binding: {}",
+            repository, binding_id
+        );
+        self.repository
+            .remove_extractor_binding(repository, binding_id, clear_state)
+            .await
+            .map_err(DataRepositoryError::Persistence)
+    }
+
+    pub async fn binding_for_index(
+        &self,
+        repository: &str,
+        index_name: &str,
+    ) -> Result<Option<ExtractorBinding>, DataRepositoryError> {
+        self.repository
+            .binding_for_index(repository, index_name)
+            .await
+            .map_err(DataRepositoryError::Persistence)
+    }
+
+    pub async fn content_processed_by_binding(
+        &self,
+        repository: &str,
+        binding_id: &str,
+    ) -> Result<Vec<Content<String>>, DataRepositoryError> {
+        self.repository
+            .content_processed_by_binding(repository, binding_id)
+            .await
+            .map_err(DataRepositoryError::Persistence)
+    }
+
+    pub async fn update_binding_params(
+        &self,
+        repository: &str,
+        binding_id: &str,
+        input_params: serde_json::Value,
+    ) -> Result<(), DataRepositoryError> {
+        self.repository
+            .update_binding_params(repository, binding_id, input_params)
+            .await
+            .map_err(DataRepositoryError::Persistence)
+    }
+
     pub async fn add_extractor_binding(
         &self,
         repository: &str,
         extractor: ExtractorBinding,
     ) -> Result<(), DataRepositoryError> {
         info!(
-            "adding extractor binding: repository: {}, extractor: {}, index: {}",
-            repository, extractor.extractor_name, extractor.index_name
+            "adding extractor binding: repository: {}, extractor: {}, indexes: {}",
+            repository,
+            extractor.extractor_name,
+            extractor.output_indexes.join(", ")
         );
+        extractor.validate()?;
         self.create_index(repository, &extractor).await?;
         let mut data_repository = self
             .repository
@@ -151,10 +425,14 @@ impl DataRepositoryManager {
             .await
             .unwrap();
         for ex in &data_repository.extractor_bindings {
-            if extractor.index_name == ex.index_name {
+            if let Some(index_name) = extractor
+                .output_indexes
+                .iter()
+                .find(|index_name| ex.output_indexes.contains(index_name))
+            {
                 return Err(DataRepositoryError::NotAllowed(format!(
                     "index with name `{}` already exists",
-                    extractor.index_name,
+                    index_name,
                 )));
             }
         }
@@ -172,7 +450,8 @@ impl DataRepositoryManager {
         self.repository
             .add_content(repo_name, texts)
             .await
-            .map_err(DataRepositoryError::Persistence)
+            .map_err(DataRepositoryError::Persistence)?;
+        Ok(())
     }
 
     pub async fn search(
@@ -188,6 +467,87 @@ impl DataRepositoryManager {
             .map_err(DataRepositoryError::RetrievalError)
     }
 
+    pub async fn list_content(
+        &self,
+        repository: &str,
+        limit: u64,
+        after_id: Option<&str>,
+        order: ContentOrder,
+    ) -> Result<Vec<Content<String>>, DataRepositoryError> {
+        self.repository
+            .list_content(repository, limit, after_id, order)
+            .await
+            .map_err(DataRepositoryError::Persistence)
+    }
+
+    pub async fn content_by_ids(
+        &self,
+        repository: &str,
+        ids: &[String],
+    ) -> Result<Vec<Content<String>>, DataRepositoryError> {
+        self.repository
+            .content_by_ids(repository, ids)
+            .await
+            .map_err(DataRepositoryError::Persistence)
+    }
+
+    pub async fn patch_content_metadata(
+        &self,
+        repository: &str,
+        content_id: &str,
+        patch: json_patch::Patch,
+    ) -> Result<(), DataRepositoryError> {
+        self.repository
+            .patch_content_metadata(repository, content_id, patch)
+            .await
+            .map_err(DataRepositoryError::Persistence)
+    }
+
+    pub async fn apply_tag_rules(
+        &self,
+        repository: &str,
+        rules: Vec<(Vec<ExtractorFilter>, String, serde_json::Value)>,
+    ) -> Result<Vec<u64>, DataRepositoryError> {
+        self.repository
+            .apply_tag_rules(repository, rules)
+            .await
+            .map_err(DataRepositoryError::Persistence)
+    }
+
+    pub async fn search_all_content(
+        &self,
+        query: &str,
+        pagination: Pagination,
+    ) -> Result<(Vec<Content<String>>, u64), DataRepositoryError> {
+        self.repository
+            .search_all_content(query, pagination)
+            .await
+            .map_err(DataRepositoryError::Persistence)
+    }
+
+    pub async fn attributes_for_contents(
+        &self,
+        repository: &str,
+        content_ids: &[String],
+    ) -> Result<HashMap<String, Vec<ExtractedAttributes>>, DataRepositoryError> {
+        self.repository
+            .attributes_for_contents(repository, content_ids)
+            .await
+            .map_err(DataRepositoryError::Persistence)
+    }
+
+    pub async fn delete_extracted_attributes(
+        &self,
+        repository: &str,
+        index_name: &str,
+        content_id: Option<&str>,
+    ) -> Result<u64, DataRepositoryError> {
+        self.repository
+            .delete_extracted_attributes(repository, index_name, content_id)
+            .await
+            .map_err(DataRepositoryError::Persistence)
+    }
+
     pub async fn attribute_lookup(
         &self,
         repository: &str,
@@ -199,6 +559,13 @@ impl DataRepositoryManager {
             .await
     }
 
+    pub async fn list_executors(&self) -> Result<Vec<Executor>, DataRepositoryError> {
+        self.repository
+            .list_live_executors(EXECUTOR_HEARTBEAT_TIMEOUT)
+            .await
+            .map_err(DataRepositoryError::Persistence)
+    }
+
     pub async fn list_extractors(&self) -> Result<Vec<ExtractorConfig>, DataRepositoryError> {
         let extractors = self
             .repository
@@ -208,6 +575,22 @@ impl DataRepositoryManager {
         Ok(extractors)
     }
 
+    pub async fn list_extractors_including_deleted(
+        &self,
+    ) -> Result<Vec<ExtractorConfig>, DataRepositoryError> {
+        self.repository
+            .list_extractors_including_deleted()
+            .await
+            .map_err(DataRepositoryError::Persistence)
+    }
+
+    pub async fn delete_extractor(&self, extractor_name: &str) -> Result<(), DataRepositoryError> {
+        self.repository
+            .delete_extractor(extractor_name)
+            .await
+            .map_err(DataRepositoryError::Persistence)
+    }
+
     pub async fn add_events(
         &self,
         repository: &str,
@@ -219,21 +602,88 @@ impl DataRepositoryManager {
             .map_err(DataRepositoryError::Persistence)
     }
 
+    /// A sink which writes the events pushed to it in batches, see `EventSink`.
+    pub fn event_sink(&self, repository: &str) -> EventSink {
+        self.repository.event_sink(repository)
+    }
+
     pub async fn list_events(&self, repository: &str) -> Result<Vec<Event>, DataRepositoryError> {
         self.repository
             .list_events(repository)
             .await
             .map_err(DataRepositoryError::Persistence)
     }
+
+    pub async fn delete_events(
+        &self,
+        repository: &str,
+        ids: Option<Vec<String>>,
+    ) -> Result<u64, DataRepositoryError> {
+        self.repository
+            .delete_events(repository, ids)
+            .await
+            .map_err(DataRepositoryError::Persistence)
+    }
+
+    /// Syncs all the data connectors of a repository, returning the ids of the
+    /// content they added.
+    pub async fn sync_connectors(
+        &self,
+        repository: &str,
+    ) -> Result<Vec<String>, DataRepositoryError> {
+        let data_repository = self.get(repository).await?;
+        let now = self.repository.clock().now() as i64;
+        let mut content_ids = Vec::new();
+        for connector in &data_repository.data_connectors {
+            content_ids.extend(
+                data_connectors::sync(&self.repository, repository, &connector.source).await?,
+            );
+            self.repository
+                .record_connector_sync(repository, &connector.source, now)
+                .await?;
+        }
+        Ok(content_ids)
+    }
+
+    /// Syncs the data connectors which are due to on their interval, returning
+    /// the names of the repositories they added content to. A connector which
+    /// fails to sync is tried again at its next interval.
+    pub async fn sync_due_connectors(&self) -> Result<Vec<String>, DataRepositoryError> {
+        let now = self.repository.clock().now() as i64;
+        let mut synced_repositories: Vec<String> = Vec::new();
+        for (repository, connector) in self.repository.connectors_due_for_sync(now).await? {
+            match data_connectors::sync(&self.repository, &repository, &connector.source).await {
+                Ok(content_ids) => {
+                    if !content_ids.is_empty() && !synced_repositories.contains(&repository) {
+                        synced_repositories.push(repository.clone());
+                    }
+                }
+                Err(err) => error!(
+                    "unable to sync {:?} of repository {}: {}",
+                    connector.source, repository, err
+                ),
+            }
+            self.repository
+                .record_connector_sync(&repository, &connector.source, now)
+                .await?;
+        }
+        Ok(synced_repositories)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
+    use std::fs;
+
+    use nanoid::nanoid;
+
+    use crate::clock::MockClock;
 
     use crate::persistence::{DataConnector, Event, ExtractorBinding, SourceType};
     use crate::test_util;
     use crate::test_util::db_utils::{DEFAULT_TEST_EXTRACTOR, DEFAULT_TEST_REPOSITORY};
+    use crate::test_util::vectordb_utils::FakeVectorDb;
 
     use serde_json::json;
 
@@ -261,6 +711,8 @@ mod tests {
                 source: SourceType::GoogleContact {
                     metadata: Some("data_connector_meta".to_string()),
                 },
+                sync_interval_secs: None,
+                last_sync: None,
             }],
         };
         repository_manager.create(&repository).await.unwrap();
@@ -320,4 +772,89 @@ mod tests {
         //    .unwrap();
         //assert_eq!(search_results.len(), 2);
     }
+
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn test_sync_due_connectors() {
+        let db = test_util::db_utils::create_db().await.unwrap();
+        let clock = Arc::new(MockClock::new(1000));
+        let repository = Arc::new(Repository::new_with_db(db).with_clock(clock.clone()));
+        let server_config = Arc::new(ServerConfig {
+            extractors: vec![],
+            ..Default::default()
+        });
+        let repository_manager = DataRepositoryManager::new(
+            repository.clone(),
+            Arc::new(VectorIndexManager::new(
+                server_config,
+                repository.clone(),
+                Arc::new(FakeVectorDb::default()),
+            )),
+            Arc::new(AttributeIndexManager::new(repository.clone())),
+        )
+        .await
+        .unwrap();
+        let dir = std::env::temp_dir().join(format!("indexify-sync-{}", nanoid!()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("a.txt"), "hello").unwrap();
+        let local_fs = |sync_interval_secs| DataConnector {
+            source: SourceType::LocalFs {
+                path: dir.to_string_lossy().to_string(),
+                glob: None,
+            },
+            sync_interval_secs,
+            last_sync: None,
+        };
+        repository_manager
+            .create(&DataRepository {
+                name: "scheduled".to_string(),
+                extractor_bindings: vec![],
+                metadata: HashMap::new(),
+                data_connectors: vec![local_fs(Some(60))],
+            })
+            .await
+            .unwrap();
+        repository_manager
+            .create(&DataRepository {
+                name: "on_request".to_string(),
+                extractor_bindings: vec![],
+                metadata: HashMap::new(),
+                data_connectors: vec![local_fs(None)],
+            })
+            .await
+            .unwrap();
+
+        // Only the connector with an interval syncs on its own
+        assert_eq!(
+            vec!["scheduled".to_string()],
+            repository_manager.sync_due_connectors().await.unwrap()
+        );
+        assert_eq!(1, repository.count_content("scheduled").await.unwrap());
+        assert_eq!(0, repository.count_content("on_request").await.unwrap());
+        let scheduled = repository_manager.get("scheduled").await.unwrap();
+        assert_eq!(Some(1000), scheduled.data_connectors[0].last_sync);
+
+        // and not again until its interval passed
+        fs::write(dir.join("b.txt"), "world").unwrap();
+        clock.advance(30);
+        assert!(repository_manager
+            .sync_due_connectors()
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(1, repository.count_content("scheduled").await.unwrap());
+        clock.advance(30);
+        repository_manager.sync_due_connectors().await.unwrap();
+        assert_eq!(2, repository.count_content("scheduled").await.unwrap());
+
+        let ids = repository_manager
+            .sync_connectors("on_request")
+            .await
+            .unwrap();
+        assert_eq!(2, ids.len());
+        assert_eq!(2, repository.count_content("on_request").await.unwrap());
+        let on_request = repository_manager.get("on_request").await.unwrap();
+        assert_eq!(Some(1060), on_request.data_connectors[0].last_sync);
+        fs::remove_dir_all(&dir).unwrap();
+    }
 }
@@ -10,12 +10,17 @@ pub struct Model {
     pub id: String,
     #[sea_orm(column_type = "Text")]
     pub text: String,
+    #[sea_orm(column_type = "Binary(BlobSize::Blob(None))", nullable)]
+    pub bytes: Option<Vec<u8>>,
     pub content_type: String,
     #[sea_orm(column_type = "JsonBinary", nullable)]
     pub metadata: Option<Json>,
     pub repository_id: String,
     #[sea_orm(column_type = "JsonBinary", nullable)]
     pub extractor_bindings_state: Option<Json>,
+    pub source: Option<String>,
+    pub external_id: Option<String>,
+    pub created_at: i64,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
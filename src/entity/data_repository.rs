@@ -14,6 +14,7 @@ pub struct Model {
     pub metadata: Option<Json>,
     #[sea_orm(column_type = "JsonBinary", nullable)]
     pub data_connectors: Option<Json>,
+    pub deleted_at: Option<i64>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -13,6 +13,8 @@ pub struct Model {
     pub unix_time_stamp: i64,
     #[sea_orm(column_type = "JsonBinary", nullable)]
     pub metadata: Option<Json>,
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub extractor_bindings_state: Option<Json>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -13,6 +13,7 @@ pub struct Model {
     pub description: String,
     #[sea_orm(column_type = "JsonBinary")]
     pub input_params: Json,
+    pub deleted_at: Option<i64>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
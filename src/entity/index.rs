@@ -12,6 +12,10 @@ pub struct Model {
     pub extractor_name: String,
     pub index_type: String,
     pub repository_id: String,
+    pub last_updated_at: Option<i64>,
+    pub dim: Option<i64>,
+    pub normalize: bool,
+    pub distance: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
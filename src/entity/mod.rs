@@ -3,11 +3,15 @@
 pub mod prelude;
 
 pub mod attributes_index;
+pub mod attributes_index_dryrun;
 pub mod content;
+pub mod data_connector_state;
 pub mod data_repository;
 pub mod events;
+pub mod executors;
 pub mod extraction_event;
 pub mod extractors;
 pub mod index;
 pub mod index_chunks;
+pub mod index_chunks_dryrun;
 pub mod work;
@@ -16,6 +16,12 @@ pub struct Model {
     #[sea_orm(column_type = "JsonBinary")]
     pub extractor_params: Json,
     pub repository_id: String,
+    pub attempts: i32,
+    pub dry_run: bool,
+    pub priority: i32,
+    pub created_at: i64,
+    pub leased_until: Option<i64>,
+    pub target: String,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
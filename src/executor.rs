@@ -2,8 +2,8 @@ use crate::{
     api::IndexifyAPIError,
     attribute_index::AttributeIndexManager,
     extractors::{self, ExtractorTS},
-    persistence::{ExtractedAttributes, Work, WorkState},
-    persistence::{ExtractorConfig, ExtractorType, Repository},
+    persistence::{Chunk, Content, ExtractedAttributes, Text, Work, WorkState},
+    persistence::{ExtractionTarget, ExtractorConfig, ExtractorType, Repository},
     vector_index::VectorIndexManager,
     vectordbs, ExecutorInfo, ServerConfig, SyncExecutor, SyncWorkerResponse,
 };
@@ -20,6 +20,10 @@ use tokio::{signal, sync::mpsc};
 use tracing::error;
 use tracing::info;
 
+/// The largest batch of content `add_content` extracts inline. Larger batches are
+/// always left to the coordinator.
+const INLINE_EXTRACTION_MAX_CONTENT: usize = 1;
+
 struct WorkStore {
     allocated_work: Arc<RwLock<HashMap<String, Work>>>,
 }
@@ -65,7 +69,7 @@ pub struct ExtractorExecutor {
 
 impl ExtractorExecutor {
     pub async fn new(config: Arc<ServerConfig>) -> Result<Self> {
-        let repository = Arc::new(Repository::new(&config.db_url).await?);
+        let repository = Arc::new(Repository::from_config(&config).await?);
         let executor_id = get_host_name(config.clone())?;
         let vector_db = vectordbs::create_vectordb(config.index_config.clone())?;
         let vector_index_manager = Arc::new(VectorIndexManager::new(
@@ -203,59 +207,150 @@ impl ExtractorExecutor {
                 "performing work: {}, extractor: {}",
                 &work.id, &work.extractor
             );
-            let extractor = self
-                .extractors
-                .get(&work.extractor)
-                .unwrap()
-                .value()
-                .clone();
-            let content = self
+            self.extract(&work).await?;
+            self.work_store
+                .update_work_state(&work.id, WorkState::Completed);
+        }
+        Ok(())
+    }
+
+    /// Adds content to a repository. With `inline_extraction` a single content is
+    /// run through the repository's bindings before returning, instead of waiting
+    /// for the coordinator to hand out work for it. Batches, and bindings whose
+    /// extractor isn't available on this executor, are left to the coordinator.
+    pub async fn add_content(
+        &self,
+        repository: &str,
+        texts: Vec<Text>,
+        inline_extraction: bool,
+    ) -> Result<(), anyhow::Error> {
+        let inline_extraction = inline_extraction && texts.len() <= INLINE_EXTRACTION_MAX_CONTENT;
+        let content_ids: Vec<String> = texts.iter().map(|text| text.id.clone()).collect();
+        self.repository.add_content(repository, texts).await?;
+        if !inline_extraction {
+            return Ok(());
+        }
+        let extractor_bindings = self
+            .repository
+            .repository_by_name(repository)
+            .await?
+            .extractor_bindings;
+        for extractor_binding in extractor_bindings
+            .iter()
+            .filter(|binding| self.extractors.contains_key(&binding.extractor_name))
+        {
+            for content_id in &content_ids {
+                let content_list = self
+                    .repository
+                    .content_with_unapplied_extractor(
+                        repository,
+                        extractor_binding,
+                        Some(content_id),
+                    )
+                    .await?;
+                for content in content_list {
+                    for index_name in &extractor_binding.output_indexes {
+                        let work = Work::new(
+                            &content.id,
+                            repository,
+                            index_name,
+                            &extractor_binding.extractor_name,
+                            &extractor_binding.input_params,
+                            Some(&self.executor_id),
+                        );
+                        self.extract(&work).await?;
+                    }
+                    self.repository
+                        .mark_content_as_processed(&content.id, &extractor_binding.id)
+                        .await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn extract(&self, work: &Work) -> Result<(), anyhow::Error> {
+        let extractor = self
+            .extractors
+            .get(&work.extractor)
+            .unwrap()
+            .value()
+            .clone();
+        let content = match work.target {
+            ExtractionTarget::Content => self
                 .repository
                 .content_from_repo(&work.content_id, &work.repository_id)
                 .await
-                .map_err(|e| anyhow!(e.to_string()))?;
-
-            if let ExtractorType::Embedding { .. } = extractor.info()?.extractor_type {
-                info!(
-                    "extracting embedding - repository: {}, extractor: {}, index: {}, content id: {}",
-                    &work.repository_id, &work.extractor, &work.index_name, &content.id
-                );
-                let extracted_embeddings = extractor
-                    .extract_embedding(vec![content.clone()], work.extractor_params.clone())?;
+                .map_err(|e| anyhow!(e.to_string()))?,
+            // Events are extracted like text content
+            ExtractionTarget::Events => {
+                let event = self
+                    .repository
+                    .event_by_id(&work.repository_id, &work.content_id)
+                    .await
+                    .map_err(|e| anyhow!(e.to_string()))?;
+                Content::new(event.id, event.message, event.metadata)
+            }
+        };
+        if let ExtractorType::Embedding { .. } = extractor.info()?.extractor_type {
+            info!(
+                "extracting embedding - repository: {}, extractor: {}, index: {}, content id: {}",
+                &work.repository_id, &work.extractor, &work.index_name, &content.id
+            );
+            let extracted_embeddings = extractor
+                .extract_embedding(vec![content.clone()], work.extractor_params.clone())?;
+            if work.dry_run {
+                let chunks = extracted_embeddings
+                    .into_iter()
+                    .map(|e| Chunk::new(e.text, e.content_id))
+                    .collect();
+                self.repository
+                    .create_dry_run_chunks(chunks, &work.index_name, &work.extractor)
+                    .await?;
+            } else {
                 self.vector_index_manager
                     .add_embedding(&work.repository_id, &work.index_name, extracted_embeddings)
                     .await?;
-                self.work_store
-                    .update_work_state(&work.id, WorkState::Completed);
             }
+        }
 
-            if let ExtractorType::Attributes { .. } = extractor.info()?.extractor_type {
-                info!(
-                    "extracting attributes - repository: {}, extractor: {}, index: {}, content id: {}",
-                    &work.repository_id, &work.extractor, &work.index_name, &content.id
-                );
-                let extracted_attributes = extractor
-                    .extract_attributes(vec![content], work.extractor_params.clone())?
-                    .into_iter()
-                    .map(|d| {
-                        ExtractedAttributes::new(
-                            &d.content_id,
-                            d.json.unwrap_or_default(),
-                            &work.extractor,
-                        )
-                    })
-                    .collect::<Vec<ExtractedAttributes>>();
-                for extracted_attribute in &extracted_attributes {
-                    self.attribute_index_manager
-                        .add_index(
+        // Classifications are stored like attributes, see `ExtractorType::Classification`
+        if let ExtractorType::Attributes { .. } | ExtractorType::Classification { .. } =
+            extractor.info()?.extractor_type
+        {
+            info!(
+                "extracting attributes - repository: {}, extractor: {}, index: {}, content id: {}",
+                &work.repository_id, &work.extractor, &work.index_name, &content.id
+            );
+            let extracted_attributes = extractor
+                .extract_attributes(vec![content], work.extractor_params.clone())?
+                .into_iter()
+                .map(|d| {
+                    ExtractedAttributes::new(
+                        &d.content_id,
+                        d.json.unwrap_or_default(),
+                        &work.extractor,
+                    )
+                })
+                .collect::<Vec<ExtractedAttributes>>();
+            for extracted_attribute in &extracted_attributes {
+                if work.dry_run {
+                    self.repository
+                        .add_dry_run_attributes(
                             &work.repository_id,
                             &work.index_name,
                             extracted_attribute.clone(),
                         )
                         .await?;
+                    continue;
                 }
-                self.work_store
-                    .update_work_state(&work.id, WorkState::Completed);
+                self.attribute_index_manager
+                    .add_index(
+                        &work.repository_id,
+                        &work.index_name,
+                        extracted_attribute.clone(),
+                    )
+                    .await?;
             }
         }
         Ok(())
@@ -384,3 +479,148 @@ async fn shutdown_signal(tx: mpsc::Sender<TickerMessage>) {
     }
     info!("signal received, shutting down server gracefully");
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::{
+        extractors::{AttributeData, ExtractedEmbeddings, Extractor},
+        persistence::{Content, DataRepository, ExtractorBinding},
+        test_util::{db_utils::create_db, vectordb_utils::FakeVectorDb},
+        vectordbs::IndexDistance,
+    };
+
+    use super::*;
+
+    // Embeds every content as a single chunk of its whole text
+    struct FakeEmbedder;
+
+    impl Extractor for FakeEmbedder {
+        fn info(&self) -> Result<ExtractorConfig, anyhow::Error> {
+            Ok(ExtractorConfig {
+                name: "embedder".into(),
+                extractor_type: ExtractorType::Embedding {
+                    dim: 2,
+                    distance: IndexDistance::Cosine,
+                },
+                ..Default::default()
+            })
+        }
+
+        fn extract_embedding(
+            &self,
+            content: Vec<Content<String>>,
+            _input_params: serde_json::Value,
+        ) -> Result<Vec<ExtractedEmbeddings>, anyhow::Error> {
+            Ok(content
+                .into_iter()
+                .map(|c| ExtractedEmbeddings {
+                    content_id: c.id,
+                    text: c.content,
+                    embeddings: vec![1.0, 0.0],
+                })
+                .collect())
+        }
+
+        fn extract_embedding_query(&self, _query: &str) -> Result<Vec<f32>, anyhow::Error> {
+            unimplemented!()
+        }
+
+        fn extract_attributes(
+            &self,
+            _content: Vec<Content<String>>,
+            _input_params: serde_json::Value,
+        ) -> Result<Vec<AttributeData>, anyhow::Error> {
+            unimplemented!()
+        }
+    }
+
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn test_add_content_inline_extraction() {
+        let db = create_db().await.unwrap();
+        let repository = Arc::new(Repository::new_with_db(db));
+        let config = Arc::new(ServerConfig {
+            extractors: vec![],
+            ..Default::default()
+        });
+        let fake_vectordb = Arc::new(FakeVectorDb::default());
+        let vectordb: vectordbs::VectorDBTS = fake_vectordb.clone();
+        let vector_index_manager = Arc::new(VectorIndexManager::new(
+            config.clone(),
+            repository.clone(),
+            vectordb,
+        ));
+        let attribute_index_manager = Arc::new(AttributeIndexManager::new(repository.clone()));
+        let executor = ExtractorExecutor::new_test(
+            repository.clone(),
+            config,
+            vector_index_manager.clone(),
+            attribute_index_manager,
+        )
+        .unwrap();
+        let extractor: ExtractorTS = Arc::new(FakeEmbedder);
+        executor
+            .extractors
+            .insert("embedder".into(), extractor.clone());
+
+        let binding = ExtractorBinding::new(
+            "test",
+            "embedder".into(),
+            "embeddings".into(),
+            vec![],
+            serde_json::json!({}),
+        );
+        repository
+            .upsert_repository(DataRepository {
+                name: "test".into(),
+                data_connectors: vec![],
+                extractor_bindings: vec![binding.clone()],
+                metadata: HashMap::new(),
+            })
+            .await
+            .unwrap();
+        vector_index_manager
+            .create_index("test", "embeddings", extractor.info().unwrap())
+            .await
+            .unwrap();
+
+        let text = Text::from_text("test", "hello world", HashMap::new());
+        executor
+            .add_content("test", vec![text.clone()], true)
+            .await
+            .unwrap();
+        let chunk = Chunk::new(text.text.clone(), text.id.clone());
+        let stored = repository.chunk_with_id(&chunk.chunk_id).await.unwrap();
+        assert_eq!(text.id, stored.content_id);
+        assert_eq!(
+            vec![chunk.chunk_id],
+            fake_vectordb.added.lock().unwrap()["test-embeddings"]
+        );
+        assert!(repository
+            .content_with_unapplied_extractor("test", &binding, None)
+            .await
+            .unwrap()
+            .is_empty());
+
+        // Batches are left to the coordinator
+        let texts = vec![
+            Text::from_text("test", "one", HashMap::new()),
+            Text::from_text("test", "two", HashMap::new()),
+        ];
+        executor.add_content("test", texts, true).await.unwrap();
+        assert_eq!(
+            1,
+            fake_vectordb.added.lock().unwrap()["test-embeddings"].len()
+        );
+        assert_eq!(
+            2,
+            repository
+                .content_with_unapplied_extractor("test", &binding, None)
+                .await
+                .unwrap()
+                .len()
+        );
+    }
+}
@@ -141,6 +141,10 @@ impl Extractor for PythonDriver {
                     let schema: String = info.getattr(py, "output_schema")?.extract(py)?;
                     Ok(ExtractorType::Attributes { schema })
                 }
+                "classification" => {
+                    let labels: Vec<String> = info.getattr(py, "output_schema")?.extract(py)?;
+                    Ok(ExtractorType::Classification { labels })
+                }
                 _ => Err(anyhow!("unsupported output datatype")),
             }?;
             let input_params =
@@ -0,0 +1,75 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use tracing::error;
+
+use crate::persistence::Text;
+
+/// Content added to a repository while the database couldn't be reached.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IngestLogEntry {
+    pub repository: String,
+    pub texts: Vec<Text>,
+}
+
+// An append-only file with one JSON entry per line, holding the content to add
+// once the database is reachable again.
+pub struct IngestLog {
+    path: PathBuf,
+}
+
+impl IngestLog {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Appends an entry, which is on disk when this returns.
+    pub fn append(&self, entry: &IngestLogEntry) -> io::Result<()> {
+        let mut line = serde_json::to_vec(entry)?;
+        line.push(b'\n');
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        file.write_all(&line)?;
+        file.sync_data()
+    }
+
+    /// The entries in the order they were appended. A log which doesn't exist yet
+    /// is empty.
+    pub fn entries(&self) -> io::Result<Vec<IngestLogEntry>> {
+        let file = match File::open(&self.path) {
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            file => file?,
+        };
+        let mut entries = Vec::new();
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            if line.is_empty() {
+                continue;
+            }
+            // Only the last line can be cut short, by a crash while appending it
+            match serde_json::from_str(&line) {
+                Ok(entry) => entries.push(entry),
+                Err(err) => error!("skipping unreadable ingest log entry: {}", err),
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Replaces the entries of the log. The new log is written next to the old one
+    /// and moved over it, so a crash leaves either of them whole.
+    pub fn rewrite(&self, entries: &[IngestLogEntry]) -> io::Result<()> {
+        let tmp_path = self.path.with_extension("tmp");
+        let mut file = File::create(&tmp_path)?;
+        for entry in entries {
+            let mut line = serde_json::to_vec(entry)?;
+            line.push(b'\n');
+            file.write_all(&line)?;
+        }
+        file.sync_data()?;
+        fs::rename(tmp_path, &self.path)
+    }
+}
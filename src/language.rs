@@ -0,0 +1,59 @@
+use std::cmp::Reverse;
+
+// Common function words of each language, text is attributed to the language
+// whose words it uses most. Enough to tell apart documents of a few sentences.
+const STOPWORDS: &[(&str, &[&str])] = &[
+    (
+        "en",
+        &[
+            "the", "and", "is", "are", "was", "of", "to", "in", "that", "it", "with", "for",
+            "this", "you", "not", "be", "have", "on",
+        ],
+    ),
+    (
+        "fr",
+        &[
+            "le", "la", "les", "et", "est", "sont", "des", "une", "un", "du", "dans", "pour",
+            "pas", "sur", "avec", "je", "vous", "nous", "il", "elle", "ce", "au",
+        ],
+    ),
+    (
+        "de",
+        &[
+            "der", "die", "das", "und", "ist", "sind", "nicht", "ein", "eine", "zu", "mit", "den",
+            "von", "ich", "sie", "auf", "für",
+        ],
+    ),
+    (
+        "es",
+        &[
+            "el", "los", "las", "y", "es", "son", "una", "por", "con", "para", "del", "se", "como",
+            "pero", "muy", "yo",
+        ],
+    ),
+];
+
+/// Guesses the language of a text, returning its ISO 639-1 code. Returns `None`
+/// when no language stands out.
+pub fn detect(text: &str) -> Option<&'static str> {
+    let words: Vec<String> = text
+        .split(|c: char| !c.is_alphabetic())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .collect();
+    let mut scores: Vec<(&str, usize)> = STOPWORDS
+        .iter()
+        .map(|(lang, stopwords)| {
+            let score = words
+                .iter()
+                .filter(|word| stopwords.contains(&word.as_str()))
+                .count();
+            (*lang, score)
+        })
+        .collect();
+    scores.sort_by_key(|(_, score)| Reverse(*score));
+    match scores.as_slice() {
+        [(lang, best), (_, second), ..] if *best > *second => Some(lang),
+        _ => None,
+    }
+}
@@ -1,11 +1,15 @@
 mod api;
 mod attribute_index;
+mod clock;
 mod coordinator;
+mod data_connectors;
 mod data_repository_manager;
 mod entity;
 mod executor;
 mod extractors;
 mod index;
+mod ingest_log;
+mod language;
 mod persistence;
 mod server;
 mod server_config;
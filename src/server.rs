@@ -1,14 +1,18 @@
 use crate::attribute_index::AttributeIndexManager;
-use crate::data_repository_manager::DataRepositoryManager;
-use crate::persistence::Repository;
+use crate::data_repository_manager::{DataRepositoryError, DataRepositoryManager};
+use crate::persistence::{EventSink, Repository, RepositoryError};
 use crate::vector_index::VectorIndexManager;
 use crate::ServerConfig;
 use crate::{api::*, persistence, vectordbs, CreateWork, CreateWorkResponse};
 
 use anyhow::Result;
-use axum::extract::{Path, Query};
-use axum::http::StatusCode;
-use axum::{extract::State, routing::get, routing::post, Json, Router};
+use axum::body::Bytes;
+use axum::extract::{BodyStream, Path, Query};
+use axum::http::{header, StatusCode};
+use axum::{
+    extract::State, routing::delete, routing::get, routing::patch, routing::post, Json, Router,
+};
+use futures::StreamExt;
 use pyo3::Python;
 use tokio::signal;
 use tracing::{error, info};
@@ -24,6 +28,11 @@ use std::sync::Arc;
 
 const DEFAULT_SEARCH_LIMIT: u64 = 5;
 
+const DEFAULT_CONTENT_LIST_LIMIT: u64 = 100;
+
+/// How often the server looks for data connectors which are due to sync.
+const CONNECTOR_SYNC_CHECK_SECS: u64 = 30;
+
 #[derive(Clone)]
 pub struct RepositoryEndpointState {
     repository_manager: Arc<DataRepositoryManager>,
@@ -36,22 +45,50 @@ pub struct RepositoryEndpointState {
             create_repository,
             list_repositories,
             get_repository,
+            delete_repository,
+            restore_repository,
+            update_repository_metadata,
+            repository_stats,
+            validate_repository,
             add_texts,
+            add_binary_content,
+            upload_content,
+            list_content,
+            raw_content,
+            content_by_ids,
+            patch_content_metadata,
+            apply_tag_rules,
+            delete_content,
+            sync_connectors,
             index_search,
+            search_all_content,
             list_extractors,
+            delete_extractor,
             bind_extractor,
+            remove_extractor_binding,
+            update_binding_params,
+            binding_processed_content,
+            index_binding,
+            list_indexes,
+            index_size,
+            delete_index,
+            reconcile_index,
             list_events,
             add_events,
+            stream_events,
+            delete_events,
             attribute_lookup,
+            delete_attributes,
+            content_attributes,
             list_executors
         ),
         components(
             schemas(CreateRepository, CreateRepositoryResponse, DataConnector,
                 IndexDistance, ExtractorType, ExtractorContentType,
-                SourceType, TextAddRequest, TextAdditionResponse, Text, IndexSearchResponse,
-                DocumentFragment, SearchRequest, ListRepositoriesResponse, ListExtractorsResponse
-            , ExtractorConfig, DataRepository, ExtractorBinding, ExtractorFilter, ExtractorBindRequest, ExtractorBindResponse, Executor,
-        ListEventsResponse, EventAddRequest, EventAddResponse, Event, AttributeLookupResponse, ExtractedAttributes, ListExecutorsResponse)
+                SourceType, TextAddRequest, TextAdditionResponse, ContentAdditionResponse, Text, IndexSearchResponse,
+                DocumentFragment, SearchRequest, ContentSearchRequest, ContentSearchResult, ContentSearchResponse, ListRepositoriesResponse, ListExtractorsResponse, ListExtractorsRequest, DeleteExtractorResponse
+            , ExtractorConfig, DataRepository, RepositoryStatsResponse, ConfigWarning, ValidateRepositoryResponse, DeletionReport, DeleteRepositoryRequest, DeleteRepositoryResponse, RestoreRepositoryResponse, UpdateRepositoryMetadataRequest, UpdateRepositoryMetadataResponse, DeleteContentResponse, Index, ListIndexesResponse, IndexSizeResponse, DeleteIndexResponse, ReconcileIndexRequest, ReconcileIndexResponse, Content, ContentOrder, ListContentRequest, ListContentResponse, ContentByIdsRequest, PatchContentMetadataResponse, TagRule, ApplyTagRulesRequest, ApplyTagRulesResponse, ExtractorBinding, ExtractorFilter, NullHandling, FilterMode, ExtractionTarget, ValueRange, ExtractorBindRequest, ExtractorBindResponse, RemoveExtractorBindingRequest, RemoveExtractorBindingResponse, UpdateBindingParamsRequest, UpdateBindingParamsResponse, IndexBindingResponse, Executor,
+        ListEventsResponse, SyncConnectorsResponse, EventAddRequest, EventAddResponse, EventStreamResponse, DeleteEventsRequest, DeleteEventsResponse, Event, AttributeLookupResponse, DeleteAttributesResponse, ContentAttributesRequest, ContentAttributesResponse, ExtractedAttributes, ListExecutorsResponse)
         ),
         tags(
             (name = "indexify", description = "Indexify API")
@@ -70,8 +107,18 @@ impl Server {
     }
 
     pub async fn run(&self) -> Result<()> {
-        let repository = Arc::new(Repository::new(&self.config.db_url).await?);
         let vectordb = vectordbs::create_vectordb(self.config.index_config.clone())?;
+        let repository = Arc::new(
+            Repository::from_config(&self.config)
+                .await?
+                .with_vectordb(vectordb.clone()),
+        );
+        // Adds the content buffered while the database was unavailable
+        match repository.replay_ingest_log().await {
+            Ok(0) => {}
+            Ok(batches) => info!("replayed {} batches from the ingest log", batches),
+            Err(err) => error!("unable to replay the ingest log: {}", err),
+        }
         let vector_index_manager = Arc::new(VectorIndexManager::new(
             self.config.clone(),
             repository.clone(),
@@ -94,6 +141,10 @@ impl Server {
             panic!("failed to create default repository: {}", err)
         }
         let coordinator_addr: SocketAddr = self.config.coordinator_addr.parse()?;
+        tokio::spawn(sync_due_connectors(
+            repository_manager.clone(),
+            coordinator_addr,
+        ));
         let repository_endpoint_state = RepositoryEndpointState {
             repository_manager: repository_manager.clone(),
             coordinator_addr,
@@ -107,6 +158,38 @@ impl Server {
                 "/repositories/:repository_name/extractor_bindings",
                 post(bind_extractor).with_state(repository_endpoint_state.clone()),
             )
+            .route(
+                "/repositories/:repository_name/extractor_bindings/:binding_id",
+                delete(remove_extractor_binding).with_state(repository_endpoint_state.clone()),
+            )
+            .route(
+                "/repositories/:repository_name/extractor_bindings/:binding_id/input_params",
+                post(update_binding_params).with_state(repository_endpoint_state.clone()),
+            )
+            .route(
+                "/repositories/:repository_name/extractor_bindings/:binding_id/processed_content",
+                get(binding_processed_content).with_state(repository_endpoint_state.clone()),
+            )
+            .route(
+                "/repositories/:repository_name/indexes",
+                get(list_indexes).with_state(repository_endpoint_state.clone()),
+            )
+            .route(
+                "/repositories/:repository_name/indexes/:index_name",
+                delete(delete_index).with_state(repository_endpoint_state.clone()),
+            )
+            .route(
+                "/repositories/:repository_name/indexes/:index_name/size",
+                get(index_size).with_state(repository_endpoint_state.clone()),
+            )
+            .route(
+                "/repositories/:repository_name/indexes/:index_name/reconcile",
+                post(reconcile_index).with_state(repository_endpoint_state.clone()),
+            )
+            .route(
+                "/repositories/:repository_name/indexes/:index_name/binding",
+                get(index_binding).with_state(repository_endpoint_state.clone()),
+            )
             .route(
                 "/repositories/:repository_name/add_texts",
                 post(add_texts).with_state(repository_endpoint_state.clone()),
@@ -115,14 +198,30 @@ impl Server {
                 "/repositories/:repository_name/run_extractors",
                 post(run_extractors).with_state(repository_endpoint_state.clone()),
             )
+            .route(
+                "/repositories/:repository_name/sync",
+                post(sync_connectors).with_state(repository_endpoint_state.clone()),
+            )
             .route(
                 "/repositories/:repository_name/search",
                 post(index_search).with_state(repository_endpoint_state.clone()),
             )
+            .route(
+                "/search",
+                get(search_all_content).with_state(repository_endpoint_state.clone()),
+            )
             .route(
                 "/repositories/:repository_name/attributes",
                 get(attribute_lookup).with_state(repository_endpoint_state.clone()),
             )
+            .route(
+                "/repositories/:repository_name/attributes",
+                delete(delete_attributes).with_state(repository_endpoint_state.clone()),
+            )
+            .route(
+                "/repositories/:repository_name/content_attributes",
+                post(content_attributes).with_state(repository_endpoint_state.clone()),
+            )
             .route(
                 "/repositories/:repository_name/events",
                 post(add_events).with_state(repository_endpoint_state.clone()),
@@ -131,6 +230,14 @@ impl Server {
                 "/repositories/:repository_name/events",
                 get(list_events).with_state(repository_endpoint_state.clone()),
             )
+            .route(
+                "/repositories/:repository_name/events",
+                delete(delete_events).with_state(repository_endpoint_state.clone()),
+            )
+            .route(
+                "/repositories/:repository_name/events/stream",
+                post(stream_events).with_state(repository_endpoint_state.clone()),
+            )
             .route(
                 "/repositories",
                 post(create_repository).with_state(repository_endpoint_state.clone()),
@@ -143,6 +250,58 @@ impl Server {
                 "/repositories/:repository_name",
                 get(get_repository).with_state(repository_endpoint_state.clone()),
             )
+            .route(
+                "/repositories/:repository_name",
+                delete(delete_repository).with_state(repository_endpoint_state.clone()),
+            )
+            .route(
+                "/repositories/:repository_name/metadata",
+                post(update_repository_metadata).with_state(repository_endpoint_state.clone()),
+            )
+            .route(
+                "/repositories/:repository_name/restore",
+                post(restore_repository).with_state(repository_endpoint_state.clone()),
+            )
+            .route(
+                "/repositories/:repository_name/content",
+                get(list_content).with_state(repository_endpoint_state.clone()),
+            )
+            .route(
+                "/repositories/:repository_name/binary_content",
+                post(add_binary_content).with_state(repository_endpoint_state.clone()),
+            )
+            .route(
+                "/repositories/:repository_name/upload",
+                post(upload_content).with_state(repository_endpoint_state.clone()),
+            )
+            .route(
+                "/repositories/:repository_name/content/:content_id/raw",
+                get(raw_content).with_state(repository_endpoint_state.clone()),
+            )
+            .route(
+                "/repositories/:repository_name/content/:content_id/metadata",
+                patch(patch_content_metadata).with_state(repository_endpoint_state.clone()),
+            )
+            .route(
+                "/repositories/:repository_name/content_by_ids",
+                post(content_by_ids).with_state(repository_endpoint_state.clone()),
+            )
+            .route(
+                "/repositories/:repository_name/tag_rules",
+                post(apply_tag_rules).with_state(repository_endpoint_state.clone()),
+            )
+            .route(
+                "/repositories/:repository_name/content/:content_id",
+                delete(delete_content).with_state(repository_endpoint_state.clone()),
+            )
+            .route(
+                "/repositories/:repository_name/validate",
+                get(validate_repository).with_state(repository_endpoint_state.clone()),
+            )
+            .route(
+                "/repositories/:repository_name/stats",
+                get(repository_stats).with_state(repository_endpoint_state.clone()),
+            )
             .route(
                 "/executors",
                 get(list_executors).with_state(repository_endpoint_state.clone()),
@@ -150,6 +309,10 @@ impl Server {
             .route(
                 "/extractors",
                 get(list_extractors).with_state(repository_endpoint_state.clone()),
+            )
+            .route(
+                "/extractors/:extractor_name",
+                delete(delete_extractor).with_state(repository_endpoint_state.clone()),
             );
         info!("server is listening at addr {:?}", &self.addr.to_string());
         axum::Server::bind(&self.addr)
@@ -208,6 +371,7 @@ async fn create_repository(
     get,
     path = "/repositories",
     tag = "indexify",
+    params(ListRepositoriesRequest),
     responses(
         (status = 200, description = "List of Data Repositories registered on the server", body = ListRepositoriesResponse),
         (status = INTERNAL_SERVER_ERROR, description = "Unable to sync repository")
@@ -215,17 +379,42 @@ async fn create_repository(
 )]
 async fn list_repositories(
     State(state): State<RepositoryEndpointState>,
+    Query(query): Query<ListRepositoriesRequest>,
 ) -> Result<Json<ListRepositoriesResponse>, IndexifyAPIError> {
-    let repositories = state
-        .repository_manager
-        .list_repositories()
-        .await
-        .map_err(|e| {
-            IndexifyAPIError::new(
-                StatusCode::INTERNAL_SERVER_ERROR,
-                format!("failed to list repositories: {}", e),
-            )
-        })?;
+    let repositories = match (query.metadata_key, query.metadata_value) {
+        (Some(_), Some(_)) if query.include_deleted => {
+            return Err(IndexifyAPIError::new(
+                StatusCode::BAD_REQUEST,
+                "include_deleted can't be combined with a metadata filter".into(),
+            ))
+        }
+        (Some(key), Some(value)) => {
+            let value = serde_json::from_str(&value).unwrap_or(serde_json::Value::String(value));
+            state
+                .repository_manager
+                .find_repositories_by_metadata(&key, value)
+                .await
+        }
+        (None, None) if query.include_deleted => {
+            state
+                .repository_manager
+                .list_repositories_including_deleted()
+                .await
+        }
+        (None, None) => state.repository_manager.list_repositories().await,
+        _ => {
+            return Err(IndexifyAPIError::new(
+                StatusCode::BAD_REQUEST,
+                "metadata_key and metadata_value go together".into(),
+            ))
+        }
+    }
+    .map_err(|e| {
+        IndexifyAPIError::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("failed to list repositories: {}", e),
+        )
+    })?;
     let data_repos = repositories.into_iter().map(|r| r.into()).collect();
     Ok(Json(ListRepositoriesResponse {
         repositories: data_repos,
@@ -262,262 +451,1070 @@ async fn get_repository(
 }
 
 #[utoipa::path(
-    post,
-    path = "/repositories/{repository_name}/extractor_bindings",
-    request_body = ExtractorBindRequest,
+    delete,
+    path = "/repositories/{repository_name}",
     tag = "indexify",
+    params(DeleteRepositoryRequest),
     responses(
-        (status = 200, description = "Extractor binded successfully", body = ExtractorBindResponse),
-        (status = INTERNAL_SERVER_ERROR, description = "Unable to bind extractor to repository")
+        (status = 200, description = "Repository was deleted, along with its data when purged", body = DeleteRepositoryResponse),
+        (status = 404, description = "Repository not found"),
+        (status = INTERNAL_SERVER_ERROR, description = "Unable to delete repository")
     ),
 )]
 #[axum_macros::debug_handler]
-async fn bind_extractor(
-    // TODO: shouldn't index_name be required here?
-    // FIXME: this throws a 500 when the binding already exists
-    // FIXME: also throws a 500 when the index name already exists
+async fn delete_repository(
     Path(repository_name): Path<String>,
     State(state): State<RepositoryEndpointState>,
-    Json(payload): Json<ExtractorBindRequest>,
-) -> Result<Json<ExtractorBindResponse>, IndexifyAPIError> {
-    state
+    Query(query): Query<DeleteRepositoryRequest>,
+) -> Result<Json<DeleteRepositoryResponse>, IndexifyAPIError> {
+    if !query.purge {
+        state
+            .repository_manager
+            .soft_delete_repository(&repository_name)
+            .await
+            .map_err(repository_api_error)?;
+        return Ok(Json(DeleteRepositoryResponse { deleted: None }));
+    }
+    let deleted = state
         .repository_manager
-        .add_extractor_binding(
-            &repository_name,
-            into_persistence_extractor_binding(&repository_name, payload.extractor_binding),
-        )
+        .delete_repository(&repository_name)
         .await
-        .map_err(|e| {
-            IndexifyAPIError::new(
-                StatusCode::INTERNAL_SERVER_ERROR,
-                format!("failed to bind extractor: {}", e),
-            )
-        })?;
-
-    if let Err(err) = _run_extractors(&repository_name, &state.coordinator_addr.to_string()).await {
-        error!("unable to run extractors: {}", err.to_string());
-    }
-    Ok(Json(ExtractorBindResponse {}))
+        .map_err(repository_api_error)?;
+    Ok(Json(DeleteRepositoryResponse {
+        deleted: Some(deleted.into()),
+    }))
 }
 
 #[utoipa::path(
     post,
-    path = "/repositories/{repository_name}/add_texts",
-    request_body = TextAddRequest,
+    path = "/repositories/{repository_name}/metadata",
+    request_body = UpdateRepositoryMetadataRequest,
     tag = "indexify",
     responses(
-        (status = 200, description = "Texts were successfully added to the repository", body = TextAdditionResponse),
-        (status = BAD_REQUEST, description = "Unable to add texts")
+        (status = 200, description = "Metadata of the repository was replaced", body = UpdateRepositoryMetadataResponse),
+        (status = 404, description = "Repository not found"),
+        (status = INTERNAL_SERVER_ERROR, description = "Unable to update the metadata")
     ),
 )]
 #[axum_macros::debug_handler]
-async fn add_texts(
+async fn update_repository_metadata(
     Path(repository_name): Path<String>,
     State(state): State<RepositoryEndpointState>,
-    Json(payload): Json<TextAddRequest>,
-) -> Result<Json<TextAdditionResponse>, IndexifyAPIError> {
-    let texts = payload
-        .documents
-        .iter()
-        .map(|d| persistence::Text::from_text(&repository_name, &d.text, d.metadata.clone()))
-        .collect();
+    Json(payload): Json<UpdateRepositoryMetadataRequest>,
+) -> Result<Json<UpdateRepositoryMetadataResponse>, IndexifyAPIError> {
     state
         .repository_manager
-        .add_texts(&repository_name, texts)
-        .await
-        .map_err(|e| {
-            IndexifyAPIError::new(
-                StatusCode::BAD_REQUEST,
-                format!("failed to add text: {}", e),
-            )
-        })?;
-
-    if let Err(err) = _run_extractors(&repository_name, &state.coordinator_addr.to_string()).await {
-        error!("unable to run extractors: {}", err.to_string());
-    }
-
-    Ok(Json(TextAdditionResponse::default()))
-}
-
-async fn _run_extractors(repository: &str, coordinator_addr: &str) -> Result<(), anyhow::Error> {
-    let req = CreateWork {
-        repository_name: repository.into(),
-        content: None,
-    };
-    let _resp = reqwest::Client::new()
-        .post(&format!("http://{}/create_work", coordinator_addr,))
-        .json(&req)
-        .send()
-        .await
-        .map_err(|e| anyhow::anyhow!("failed to send create work request: {}", e))?
-        .json::<CreateWorkResponse>()
-        .await?;
-    Ok(())
-}
-
-async fn run_extractors(
-    Path(repository_name): Path<String>,
-    State(state): State<RepositoryEndpointState>,
-) -> Result<Json<RunExtractorsResponse>, IndexifyAPIError> {
-    _run_extractors(&repository_name, &state.coordinator_addr.to_string())
+        .update_repository_metadata(&repository_name, payload.metadata)
         .await
-        .map_err(|e| IndexifyAPIError::new(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
-    Ok(Json(RunExtractorsResponse {}))
+        .map_err(repository_api_error)?;
+    Ok(Json(UpdateRepositoryMetadataResponse {}))
 }
 
 #[utoipa::path(
     post,
-    path = "/repositories/{repository_name}/events",
-    request_body =  EventAddRequest,
+    path = "/repositories/{repository_name}/restore",
     tag = "indexify",
     responses(
-        (status = 200, description = "Events were successfully added to the repository", body = EventAddResponse),
-        (status = BAD_REQUEST, description = "Unable to add event")
+        (status = 200, description = "Deleted repository was restored", body = RestoreRepositoryResponse),
+        (status = 404, description = "Repository not found"),
+        (status = INTERNAL_SERVER_ERROR, description = "Unable to restore repository")
     ),
 )]
 #[axum_macros::debug_handler]
-async fn add_events(
+async fn restore_repository(
     Path(repository_name): Path<String>,
     State(state): State<RepositoryEndpointState>,
-    Json(payload): Json<EventAddRequest>,
-) -> Result<Json<EventAddResponse>, IndexifyAPIError> {
-    let events = payload.events.iter().map(|m| m.clone().into()).collect();
+) -> Result<Json<RestoreRepositoryResponse>, IndexifyAPIError> {
     state
         .repository_manager
-        .add_events(&repository_name, events)
+        .restore_repository(&repository_name)
         .await
-        .map_err(|e| IndexifyAPIError::new(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
-
-    if let Err(err) = _run_extractors(&repository_name, &state.coordinator_addr.to_string()).await {
-        error!("unable to run extractors: {}", err.to_string());
-    }
-
-    Ok(Json(EventAddResponse {}))
+        .map_err(repository_api_error)?;
+    Ok(Json(RestoreRepositoryResponse {}))
 }
 
 #[utoipa::path(
     get,
-    path = "/repositories/{repository_name}/events",
+    path = "/repositories/{repository_name}/content",
     tag = "indexify",
+    params(ListContentRequest),
     responses(
-        (status = 200, description = "List of Events in a repository", body = ListEventsResponse),
-        (status = INTERNAL_SERVER_ERROR, description = "Unable to list events in repository")
+        (status = 200, description = "A page of the content of the repository", body = ListContentResponse),
+        (status = BAD_REQUEST, description = "Content in natural order can't be paged"),
+        (status = INTERNAL_SERVER_ERROR, description = "Unable to list content")
     ),
 )]
 #[axum_macros::debug_handler]
-async fn list_events(
+async fn list_content(
     Path(repository_name): Path<String>,
     State(state): State<RepositoryEndpointState>,
-) -> Result<Json<ListEventsResponse>, IndexifyAPIError> {
-    let messages = state
+    Query(query): Query<ListContentRequest>,
+) -> Result<Json<ListContentResponse>, IndexifyAPIError> {
+    let content = state
         .repository_manager
-        .list_events(&repository_name)
+        .list_content(
+            &repository_name,
+            query.limit.unwrap_or(DEFAULT_CONTENT_LIST_LIMIT),
+            query.after_id.as_deref(),
+            query.order.into(),
+        )
         .await
-        .map_err(|e| IndexifyAPIError::new(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
-        .iter()
-        .map(|m| m.to_owned().into())
+        .map_err(repository_api_error)?
+        .into_iter()
+        .map(|content| content.into())
         .collect();
-
-    Ok(Json(ListEventsResponse { messages }))
+    Ok(Json(ListContentResponse { content }))
 }
 
 #[utoipa::path(
-    get,
-    path = "/executors",
+    patch,
+    path = "/repositories/{repository_name}/content/{content_id}/metadata",
+    request_body(content = String, description = "A JSON Patch applied to the metadata of the content", content_type = "application/json-patch+json"),
     tag = "indexify",
     responses(
-        (status = 200, description = "List of currently running executors", body = ListExecutorsResponse),
-        (status = INTERNAL_SERVER_ERROR, description = "Unable to load executors")
+        (status = 200, description = "Metadata of the content was patched", body = PatchContentMetadataResponse),
+        (status = 404, description = "Content not found"),
+        (status = BAD_REQUEST, description = "The patch couldn't be applied to the metadata"),
+        (status = INTERNAL_SERVER_ERROR, description = "Unable to patch the metadata")
     ),
 )]
 #[axum_macros::debug_handler]
-async fn list_executors(
-    State(_state): State<RepositoryEndpointState>,
-) -> Result<Json<ListExecutorsResponse>, IndexifyAPIError> {
-    Ok(Json(ListExecutorsResponse { executors: vec![] }))
+async fn patch_content_metadata(
+    Path((repository_name, content_id)): Path<(String, String)>,
+    State(state): State<RepositoryEndpointState>,
+    Json(patch): Json<json_patch::Patch>,
+) -> Result<Json<PatchContentMetadataResponse>, IndexifyAPIError> {
+    state
+        .repository_manager
+        .patch_content_metadata(&repository_name, &content_id, patch)
+        .await
+        .map_err(repository_api_error)?;
+
+    // Bindings the new metadata passes the filters of extract from the content
+    if let Err(err) = _run_extractors(&repository_name, &state.coordinator_addr.to_string()).await {
+        error!("unable to run extractors: {}", err.to_string());
+    }
+    Ok(Json(PatchContentMetadataResponse {}))
 }
 
 #[utoipa::path(
-    get,
-    path = "/extractors",
+    post,
+    path = "/repositories/{repository_name}/content_by_ids",
+    request_body = ContentByIdsRequest,
     tag = "indexify",
     responses(
-        (status = 200, description = "List of extractors available", body = ListExtractorsResponse),
-        (status = INTERNAL_SERVER_ERROR, description = "Unable to search index")
+        (status = 200, description = "The content with the given ids, in their order", body = ListContentResponse),
+        (status = INTERNAL_SERVER_ERROR, description = "Unable to fetch content")
     ),
 )]
 #[axum_macros::debug_handler]
-async fn list_extractors(
+async fn content_by_ids(
+    Path(repository_name): Path<String>,
     State(state): State<RepositoryEndpointState>,
-) -> Result<Json<ListExtractorsResponse>, IndexifyAPIError> {
-    let extractors = state
+    Json(payload): Json<ContentByIdsRequest>,
+) -> Result<Json<ListContentResponse>, IndexifyAPIError> {
+    let content = state
         .repository_manager
-        .list_extractors()
+        .content_by_ids(&repository_name, &payload.ids)
         .await
-        .map_err(|e| IndexifyAPIError::new(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .map_err(repository_api_error)?
         .into_iter()
-        .map(|e| e.into())
+        .map(|content| content.into())
         .collect();
-    Ok(Json(ListExtractorsResponse { extractors }))
+    Ok(Json(ListContentResponse { content }))
 }
 
 #[utoipa::path(
     post,
-    path = "/repository/{repository_name}/search",
+    path = "/repositories/{repository_name}/tag_rules",
+    request_body = ApplyTagRulesRequest,
     tag = "indexify",
     responses(
-        (status = 200, description = "Index search results", body = IndexSearchResponse),
-        (status = INTERNAL_SERVER_ERROR, description = "Unable to search index")
+        (status = 200, description = "The content matching each rule was tagged", body = ApplyTagRulesResponse),
+        (status = BAD_REQUEST, description = "A rule has an invalid filter"),
+        (status = INTERNAL_SERVER_ERROR, description = "Unable to tag content")
     ),
 )]
 #[axum_macros::debug_handler]
-async fn index_search(
+async fn apply_tag_rules(
     Path(repository_name): Path<String>,
     State(state): State<RepositoryEndpointState>,
-    Json(query): Json<SearchRequest>,
-) -> Result<Json<IndexSearchResponse>, IndexifyAPIError> {
-    let results = state
+    Json(payload): Json<ApplyTagRulesRequest>,
+) -> Result<Json<ApplyTagRulesResponse>, IndexifyAPIError> {
+    let tagged = state
         .repository_manager
-        .search(
-            &repository_name,
-            &query.index,
-            &query.query,
-            query.k.unwrap_or(DEFAULT_SEARCH_LIMIT),
-        )
+        .apply_tag_rules(&repository_name, into_persistence_tag_rules(payload.rules))
         .await
-        .map_err(|e| IndexifyAPIError::new(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
-    let document_fragments: Vec<DocumentFragment> = results
-        .iter()
-        .map(|text| DocumentFragment {
-            text: text.text.text.clone(),
-            metadata: text.text.metadata.clone(),
-            confidence_score: text.confidence_score,
-        })
-        .collect();
-    Ok(Json(IndexSearchResponse {
-        results: document_fragments,
-    }))
+        .map_err(repository_api_error)?;
+    Ok(Json(ApplyTagRulesResponse { tagged }))
 }
 
 #[utoipa::path(
-    get,
-    path = "/repository/{repository_name}/attributes",
+    delete,
+    path = "/repositories/{repository_name}/content/{content_id}",
     tag = "indexify",
-    params(AttributeLookupRequest),
     responses(
-        (status = 200, description = "List of Events in a repository", body = AttributeLookupResponse),
-        (status = INTERNAL_SERVER_ERROR, description = "Unable to list events in repository")
+        (status = 200, description = "Content and what was extracted from it were deleted", body = DeleteContentResponse),
+        (status = 404, description = "Content not found"),
+        (status = INTERNAL_SERVER_ERROR, description = "Unable to delete content")
     ),
 )]
 #[axum_macros::debug_handler]
-async fn attribute_lookup(
-    Path(repository_name): Path<String>,
+async fn delete_content(
+    Path((repository_name, content_id)): Path<(String, String)>,
     State(state): State<RepositoryEndpointState>,
-    Query(query): Query<AttributeLookupRequest>,
-) -> Result<Json<AttributeLookupResponse>, IndexifyAPIError> {
-    let attributes = state
+) -> Result<Json<DeleteContentResponse>, IndexifyAPIError> {
+    let deleted = state
         .repository_manager
-        .attribute_lookup(&repository_name, &query.index, query.content_id.as_ref())
+        .delete_content(&repository_name, &content_id)
+        .await
+        .map_err(repository_api_error)?;
+    Ok(Json(DeleteContentResponse {
+        deleted: deleted.into(),
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/repositories/{repository_name}/validate",
+    tag = "indexify",
+    responses(
+        (status = 200, description = "Configuration problems found in the repository", body = ValidateRepositoryResponse),
+        (status = 404, description = "Repository not found"),
+        (status = INTERNAL_SERVER_ERROR, description = "Unable to validate the repository")
+    ),
+)]
+#[axum_macros::debug_handler]
+async fn validate_repository(
+    Path(repository_name): Path<String>,
+    State(state): State<RepositoryEndpointState>,
+) -> Result<Json<ValidateRepositoryResponse>, IndexifyAPIError> {
+    let warnings = state
+        .repository_manager
+        .validate_repository(&repository_name)
+        .await
+        .map_err(repository_api_error)?
+        .into_iter()
+        .map(|warning| warning.into())
+        .collect();
+    Ok(Json(ValidateRepositoryResponse { warnings }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/repositories/{repository_name}/stats",
+    tag = "indexify",
+    responses(
+        (status = 200, description = "Extraction statistics of the repository", body = RepositoryStatsResponse),
+        (status = 404, description = "Repository not found"),
+        (status = INTERNAL_SERVER_ERROR, description = "Unable to get the repository statistics")
+    ),
+)]
+#[axum_macros::debug_handler]
+async fn repository_stats(
+    Path(repository_name): Path<String>,
+    State(state): State<RepositoryEndpointState>,
+) -> Result<Json<RepositoryStatsResponse>, IndexifyAPIError> {
+    let binding_lag = state
+        .repository_manager
+        .binding_lag(&repository_name)
+        .await
+        .map_err(repository_api_error)?;
+    let extraction_progress = state
+        .repository_manager
+        .extraction_progress(&repository_name)
+        .await
+        .map_err(repository_api_error)?;
+    let content_count = state
+        .repository_manager
+        .count_content(&repository_name)
+        .await
+        .map_err(repository_api_error)?;
+    let content_count_by_type = state
+        .repository_manager
+        .count_content_by_type(&repository_name)
+        .await
+        .map_err(repository_api_error)?;
+    let content_count_by_source = state
+        .repository_manager
+        .content_counts_by_source(&repository_name)
+        .await
+        .map_err(repository_api_error)?;
+    Ok(Json(RepositoryStatsResponse {
+        binding_lag,
+        extraction_progress,
+        content_count,
+        content_count_by_type,
+        content_count_by_source,
+    }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/repositories/{repository_name}/extractor_bindings",
+    request_body = ExtractorBindRequest,
+    tag = "indexify",
+    responses(
+        (status = 200, description = "Extractor binded successfully", body = ExtractorBindResponse),
+        (status = INTERNAL_SERVER_ERROR, description = "Unable to bind extractor to repository")
+    ),
+)]
+#[axum_macros::debug_handler]
+async fn bind_extractor(
+    // TODO: shouldn't index_name be required here?
+    // FIXME: this throws a 500 when the binding already exists
+    // FIXME: also throws a 500 when the index name already exists
+    Path(repository_name): Path<String>,
+    State(state): State<RepositoryEndpointState>,
+    Json(payload): Json<ExtractorBindRequest>,
+) -> Result<Json<ExtractorBindResponse>, IndexifyAPIError> {
+    state
+        .repository_manager
+        .add_extractor_binding(
+            &repository_name,
+            into_persistence_extractor_binding(&repository_name, payload.extractor_binding),
+        )
+        .await
+        .map_err(|e| {
+            IndexifyAPIError::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("failed to bind extractor: {}", e),
+            )
+        })?;
+
+    if let Err(err) = _run_extractors(&repository_name, &state.coordinator_addr.to_string()).await {
+        error!("unable to run extractors: {}", err.to_string());
+    }
+    Ok(Json(ExtractorBindResponse {}))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/repositories/{repository_name}/extractor_bindings/{binding_id}",
+    tag = "indexify",
+    params(RemoveExtractorBindingRequest),
+    responses(
+        (status = 200, description = "Extractor binding removed successfully", body = RemoveExtractorBindingResponse),
+        (status = 404, description = "Repository or extractor binding not found"),
+        (status = INTERNAL_SERVER_ERROR, description = "Unable to remove extractor binding")
+    ),
+)]
+#[axum_macros::debug_handler]
+async fn remove_extractor_binding(
+    Path((repository_name, binding_id)): Path<(String, String)>,
+    State(state): State<RepositoryEndpointState>,
+    Query(query): Query<RemoveExtractorBindingRequest>,
+) -> Result<Json<RemoveExtractorBindingResponse>, IndexifyAPIError> {
+    state
+        .repository_manager
+        .remove_extractor_binding(&repository_name, &binding_id, query.clear_state)
+        .await
+        .map_err(repository_api_error)?;
+    Ok(Json(RemoveExtractorBindingResponse {}))
+}
+
+#[utoipa::path(
+    post,
+    path = "/repositories/{repository_name}/extractor_bindings/{binding_id}/input_params",
+    request_body = UpdateBindingParamsRequest,
+    tag = "indexify",
+    responses(
+        (status = 200, description = "Input params of the binding were replaced", body = UpdateBindingParamsResponse),
+        (status = 404, description = "Repository or extractor binding not found"),
+        (status = INTERNAL_SERVER_ERROR, description = "Unable to update the input params")
+    ),
+)]
+#[axum_macros::debug_handler]
+async fn update_binding_params(
+    Path((repository_name, binding_id)): Path<(String, String)>,
+    State(state): State<RepositoryEndpointState>,
+    Json(payload): Json<UpdateBindingParamsRequest>,
+) -> Result<Json<UpdateBindingParamsResponse>, IndexifyAPIError> {
+    state
+        .repository_manager
+        .update_binding_params(&repository_name, &binding_id, payload.input_params)
+        .await
+        .map_err(repository_api_error)?;
+    Ok(Json(UpdateBindingParamsResponse {}))
+}
+
+#[utoipa::path(
+    get,
+    path = "/repositories/{repository_name}/indexes",
+    tag = "indexify",
+    responses(
+        (status = 200, description = "Indexes of the repository", body = ListIndexesResponse),
+        (status = INTERNAL_SERVER_ERROR, description = "Unable to list indexes")
+    ),
+)]
+#[axum_macros::debug_handler]
+async fn list_indexes(
+    Path(repository_name): Path<String>,
+    State(state): State<RepositoryEndpointState>,
+) -> Result<Json<ListIndexesResponse>, IndexifyAPIError> {
+    let indexes = state
+        .repository_manager
+        .list_indexes(&repository_name)
+        .await
+        .map_err(repository_api_error)?
+        .into_iter()
+        .map(|index| index.into())
+        .collect();
+    Ok(Json(ListIndexesResponse { indexes }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/repositories/{repository_name}/indexes/{index_name}/size",
+    tag = "indexify",
+    responses(
+        (status = 200, description = "Estimated size of the index in the vector store", body = IndexSizeResponse),
+        (status = 404, description = "Index not found"),
+        (status = INTERNAL_SERVER_ERROR, description = "Unable to estimate the size of the index")
+    ),
+)]
+#[axum_macros::debug_handler]
+async fn index_size(
+    Path((repository_name, index_name)): Path<(String, String)>,
+    State(state): State<RepositoryEndpointState>,
+) -> Result<Json<IndexSizeResponse>, IndexifyAPIError> {
+    let estimate = state
+        .repository_manager
+        .estimate_index_size(&repository_name, &index_name)
+        .await
+        .map_err(repository_api_error)?;
+    Ok(Json(estimate.into()))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/repositories/{repository_name}/indexes/{index_name}",
+    tag = "indexify",
+    responses(
+        (status = 200, description = "Index and its chunks were deleted", body = DeleteIndexResponse),
+        (status = 404, description = "Index not found"),
+        (status = INTERNAL_SERVER_ERROR, description = "Unable to delete index")
+    ),
+)]
+#[axum_macros::debug_handler]
+async fn delete_index(
+    Path((repository_name, index_name)): Path<(String, String)>,
+    State(state): State<RepositoryEndpointState>,
+) -> Result<Json<DeleteIndexResponse>, IndexifyAPIError> {
+    let deleted = state
+        .repository_manager
+        .delete_index(&repository_name, &index_name)
+        .await
+        .map_err(repository_api_error)?;
+    Ok(Json(DeleteIndexResponse {
+        deleted: deleted.into(),
+    }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/repositories/{repository_name}/indexes/{index_name}/reconcile",
+    tag = "indexify",
+    params(ReconcileIndexRequest),
+    responses(
+        (status = 200, description = "The chunks of the index compared with the vector store", body = ReconcileIndexResponse),
+        (status = 404, description = "Index not found"),
+        (status = INTERNAL_SERVER_ERROR, description = "Unable to reconcile index")
+    ),
+)]
+#[axum_macros::debug_handler]
+async fn reconcile_index(
+    Path((repository_name, index_name)): Path<(String, String)>,
+    State(state): State<RepositoryEndpointState>,
+    Query(query): Query<ReconcileIndexRequest>,
+) -> Result<Json<ReconcileIndexResponse>, IndexifyAPIError> {
+    let report = state
+        .repository_manager
+        .reconcile_index(&repository_name, &index_name, query.repair)
+        .await
+        .map_err(repository_api_error)?;
+    if query.repair && !report.missing_in_store.is_empty() {
+        if let Err(err) =
+            _run_extractors(&repository_name, &state.coordinator_addr.to_string()).await
+        {
+            error!("unable to run extractors: {}", err.to_string());
+        }
+    }
+    Ok(Json(report.into()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/repositories/{repository_name}/extractor_bindings/{binding_id}/processed_content",
+    tag = "indexify",
+    responses(
+        (status = 200, description = "Content the binding matches which it has already processed", body = ListContentResponse),
+        (status = 404, description = "Repository or extractor binding not found"),
+        (status = INTERNAL_SERVER_ERROR, description = "Unable to list the processed content")
+    ),
+)]
+#[axum_macros::debug_handler]
+async fn binding_processed_content(
+    Path((repository_name, binding_id)): Path<(String, String)>,
+    State(state): State<RepositoryEndpointState>,
+) -> Result<Json<ListContentResponse>, IndexifyAPIError> {
+    let content = state
+        .repository_manager
+        .content_processed_by_binding(&repository_name, &binding_id)
+        .await
+        .map_err(repository_api_error)?
+        .into_iter()
+        .map(|content| content.into())
+        .collect();
+    Ok(Json(ListContentResponse { content }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/repositories/{repository_name}/indexes/{index_name}/binding",
+    tag = "indexify",
+    responses(
+        (status = 200, description = "The extractor binding writing to the index", body = IndexBindingResponse),
+        (status = 404, description = "Repository not found"),
+        (status = INTERNAL_SERVER_ERROR, description = "Unable to find the binding of the index")
+    ),
+)]
+#[axum_macros::debug_handler]
+async fn index_binding(
+    Path((repository_name, index_name)): Path<(String, String)>,
+    State(state): State<RepositoryEndpointState>,
+) -> Result<Json<IndexBindingResponse>, IndexifyAPIError> {
+    let binding = state
+        .repository_manager
+        .binding_for_index(&repository_name, &index_name)
+        .await
+        .map_err(repository_api_error)?;
+    Ok(Json(IndexBindingResponse {
+        binding: binding.map(|binding| binding.into()),
+    }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/repositories/{repository_name}/add_texts",
+    request_body = TextAddRequest,
+    tag = "indexify",
+    responses(
+        (status = 200, description = "Texts were successfully added to the repository", body = TextAdditionResponse),
+        (status = BAD_REQUEST, description = "Unable to add texts")
+    ),
+)]
+#[axum_macros::debug_handler]
+async fn add_texts(
+    Path(repository_name): Path<String>,
+    State(state): State<RepositoryEndpointState>,
+    Json(payload): Json<TextAddRequest>,
+) -> Result<Json<TextAdditionResponse>, IndexifyAPIError> {
+    let texts = payload
+        .documents
+        .iter()
+        .map(|d| {
+            match (&d.source, &d.external_id) {
+                (Some(source), Some(external_id)) => persistence::Text::from_external(
+                    &repository_name,
+                    source,
+                    external_id,
+                    &d.text,
+                    d.metadata.clone(),
+                ),
+                _ => persistence::Text::from_text(&repository_name, &d.text, d.metadata.clone()),
+            }
+            .with_priority(d.priority)
+        })
+        .collect();
+    state
+        .repository_manager
+        .add_texts(&repository_name, texts)
+        .await
+        .map_err(|e| {
+            IndexifyAPIError::new(
+                StatusCode::BAD_REQUEST,
+                format!("failed to add text: {}", e),
+            )
+        })?;
+
+    if let Err(err) = _run_extractors(&repository_name, &state.coordinator_addr.to_string()).await {
+        error!("unable to run extractors: {}", err.to_string());
+    }
+
+    Ok(Json(TextAdditionResponse::default()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/repositories/{repository_name}/binary_content",
+    request_body(content = Vec<u8>, content_type = "application/octet-stream"),
+    tag = "indexify",
+    responses(
+        (status = 200, description = "Binary content was added to the repository", body = ContentAdditionResponse),
+        (status = 404, description = "Repository not found"),
+        (status = INTERNAL_SERVER_ERROR, description = "Unable to add binary content")
+    ),
+)]
+#[axum_macros::debug_handler]
+async fn add_binary_content(
+    Path(repository_name): Path<String>,
+    State(state): State<RepositoryEndpointState>,
+    body: Bytes,
+) -> Result<Json<ContentAdditionResponse>, IndexifyAPIError> {
+    let id = state
+        .repository_manager
+        .add_binary_content(&repository_name, body.to_vec())
+        .await
+        .map_err(repository_api_error)?;
+
+    if let Err(err) = _run_extractors(&repository_name, &state.coordinator_addr.to_string()).await {
+        error!("unable to run extractors: {}", err.to_string());
+    }
+
+    Ok(Json(ContentAdditionResponse { id }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/repositories/{repository_name}/upload",
+    request_body(content = Vec<u8>, content_type = "application/octet-stream"),
+    tag = "indexify",
+    responses(
+        (status = 200, description = "Content was added with the type detected from its bytes", body = ContentAdditionResponse),
+        (status = 404, description = "Repository not found"),
+        (status = INTERNAL_SERVER_ERROR, description = "Unable to add content")
+    ),
+)]
+#[axum_macros::debug_handler]
+async fn upload_content(
+    Path(repository_name): Path<String>,
+    State(state): State<RepositoryEndpointState>,
+    body: Bytes,
+) -> Result<Json<ContentAdditionResponse>, IndexifyAPIError> {
+    let id = state
+        .repository_manager
+        .add_content_auto(&repository_name, body.to_vec())
+        .await
+        .map_err(repository_api_error)?;
+
+    if let Err(err) = _run_extractors(&repository_name, &state.coordinator_addr.to_string()).await {
+        error!("unable to run extractors: {}", err.to_string());
+    }
+
+    Ok(Json(ContentAdditionResponse { id }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/repositories/{repository_name}/content/{content_id}/raw",
+    tag = "indexify",
+    responses(
+        (status = 200, description = "The bytes of the content", body = Vec<u8>, content_type = "application/octet-stream"),
+        (status = 404, description = "Content not found"),
+        (status = INTERNAL_SERVER_ERROR, description = "Unable to get content")
+    ),
+)]
+#[axum_macros::debug_handler]
+async fn raw_content(
+    Path((repository_name, content_id)): Path<(String, String)>,
+    State(state): State<RepositoryEndpointState>,
+) -> Result<([(header::HeaderName, &'static str); 1], Vec<u8>), IndexifyAPIError> {
+    let content = state
+        .repository_manager
+        .binary_content(&repository_name, &content_id)
+        .await
+        .map_err(repository_api_error)?;
+    Ok((
+        [(header::CONTENT_TYPE, "application/octet-stream")],
+        content.content,
+    ))
+}
+
+async fn _run_extractors(repository: &str, coordinator_addr: &str) -> Result<(), anyhow::Error> {
+    let req = CreateWork {
+        repository_name: repository.into(),
+        content: None,
+    };
+    let _resp = reqwest::Client::new()
+        .post(&format!("http://{}/create_work", coordinator_addr,))
+        .json(&req)
+        .send()
+        .await
+        .map_err(|e| anyhow::anyhow!("failed to send create work request: {}", e))?
+        .json::<CreateWorkResponse>()
+        .await?;
+    Ok(())
+}
+
+async fn run_extractors(
+    Path(repository_name): Path<String>,
+    State(state): State<RepositoryEndpointState>,
+) -> Result<Json<RunExtractorsResponse>, IndexifyAPIError> {
+    _run_extractors(&repository_name, &state.coordinator_addr.to_string())
+        .await
+        .map_err(|e| IndexifyAPIError::new(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(Json(RunExtractorsResponse {}))
+}
+
+#[utoipa::path(
+    post,
+    path = "/repositories/{repository_name}/sync",
+    tag = "indexify",
+    responses(
+        (status = 200, description = "Data connectors of the repository were synced", body = SyncConnectorsResponse),
+        (status = BAD_REQUEST, description = "Unable to sync data connectors")
+    ),
+)]
+#[axum_macros::debug_handler]
+async fn sync_connectors(
+    Path(repository_name): Path<String>,
+    State(state): State<RepositoryEndpointState>,
+) -> Result<Json<SyncConnectorsResponse>, IndexifyAPIError> {
+    let content_ids = state
+        .repository_manager
+        .sync_connectors(&repository_name)
+        .await
+        .map_err(|e| IndexifyAPIError::new(StatusCode::BAD_REQUEST, e.to_string()))?;
+
+    if !content_ids.is_empty() {
+        if let Err(err) =
+            _run_extractors(&repository_name, &state.coordinator_addr.to_string()).await
+        {
+            error!("unable to run extractors: {}", err.to_string());
+        }
+    }
+
+    Ok(Json(SyncConnectorsResponse { content_ids }))
+}
+
+/// Syncs the data connectors which are due to on their interval, for as long as
+/// the server runs.
+async fn sync_due_connectors(
+    repository_manager: Arc<DataRepositoryManager>,
+    coordinator_addr: SocketAddr,
+) {
+    let mut interval =
+        tokio::time::interval(std::time::Duration::from_secs(CONNECTOR_SYNC_CHECK_SECS));
+    loop {
+        interval.tick().await;
+        match repository_manager.sync_due_connectors().await {
+            Ok(repositories) => {
+                for repository in repositories {
+                    if let Err(err) =
+                        _run_extractors(&repository, &coordinator_addr.to_string()).await
+                    {
+                        error!("unable to run extractors: {}", err.to_string());
+                    }
+                }
+            }
+            Err(err) => error!("unable to sync data connectors: {}", err.to_string()),
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/repositories/{repository_name}/events",
+    request_body =  EventAddRequest,
+    tag = "indexify",
+    responses(
+        (status = 200, description = "Events were successfully added to the repository", body = EventAddResponse),
+        (status = BAD_REQUEST, description = "Unable to add event")
+    ),
+)]
+#[axum_macros::debug_handler]
+async fn add_events(
+    Path(repository_name): Path<String>,
+    State(state): State<RepositoryEndpointState>,
+    Json(payload): Json<EventAddRequest>,
+) -> Result<Json<EventAddResponse>, IndexifyAPIError> {
+    let events = payload.events.iter().map(|m| m.clone().into()).collect();
+    state
+        .repository_manager
+        .add_events(&repository_name, events)
+        .await
+        .map_err(|e| IndexifyAPIError::new(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    if let Err(err) = _run_extractors(&repository_name, &state.coordinator_addr.to_string()).await {
+        error!("unable to run extractors: {}", err.to_string());
+    }
+
+    Ok(Json(EventAddResponse {}))
+}
+
+#[utoipa::path(
+    post,
+    path = "/repositories/{repository_name}/events/stream",
+    request_body(content = String, description = "Events as newline delimited JSON", content_type = "application/x-ndjson"),
+    tag = "indexify",
+    responses(
+        (status = 200, description = "Events were successfully added to the repository", body = EventStreamResponse),
+        (status = BAD_REQUEST, description = "An event couldn't be read, the events before it were added")
+    ),
+)]
+#[axum_macros::debug_handler]
+async fn stream_events(
+    Path(repository_name): Path<String>,
+    State(state): State<RepositoryEndpointState>,
+    mut body: BodyStream,
+) -> Result<Json<EventStreamResponse>, IndexifyAPIError> {
+    let sink = state.repository_manager.event_sink(&repository_name);
+    let mut added = 0;
+    let mut buffer = Vec::new();
+    while let Some(chunk) = body.next().await {
+        let chunk =
+            chunk.map_err(|e| IndexifyAPIError::new(StatusCode::BAD_REQUEST, e.to_string()))?;
+        buffer.extend_from_slice(&chunk);
+        while let Some(end) = buffer.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = buffer.drain(..=end).collect();
+            added += push_event_line(&sink, &line).await?;
+        }
+    }
+    added += push_event_line(&sink, &buffer).await?;
+    sink.close()
+        .await
+        .map_err(|e| IndexifyAPIError::new(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    if let Err(err) = _run_extractors(&repository_name, &state.coordinator_addr.to_string()).await {
+        error!("unable to run extractors: {}", err.to_string());
+    }
+
+    Ok(Json(EventStreamResponse { added }))
+}
+
+// Pushes the event on a line of a stream, blank lines are skipped
+async fn push_event_line(sink: &EventSink, line: &[u8]) -> Result<u64, IndexifyAPIError> {
+    if line.iter().all(u8::is_ascii_whitespace) {
+        return Ok(0);
+    }
+    let event: Event = serde_json::from_slice(line).map_err(|e| {
+        IndexifyAPIError::new(StatusCode::BAD_REQUEST, format!("invalid event: {}", e))
+    })?;
+    sink.push(event.into())
+        .await
+        .map_err(|e| IndexifyAPIError::new(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(1)
+}
+
+#[utoipa::path(
+    get,
+    path = "/repositories/{repository_name}/events",
+    tag = "indexify",
+    responses(
+        (status = 200, description = "List of Events in a repository", body = ListEventsResponse),
+        (status = INTERNAL_SERVER_ERROR, description = "Unable to list events in repository")
+    ),
+)]
+#[axum_macros::debug_handler]
+async fn list_events(
+    Path(repository_name): Path<String>,
+    State(state): State<RepositoryEndpointState>,
+) -> Result<Json<ListEventsResponse>, IndexifyAPIError> {
+    let messages = state
+        .repository_manager
+        .list_events(&repository_name)
+        .await
+        .map_err(|e| IndexifyAPIError::new(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .iter()
+        .map(|m| m.to_owned().into())
+        .collect();
+
+    Ok(Json(ListEventsResponse { messages }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/repositories/{repository_name}/events",
+    request_body = DeleteEventsRequest,
+    tag = "indexify",
+    responses(
+        (status = 200, description = "Events were deleted from the repository", body = DeleteEventsResponse),
+        (status = INTERNAL_SERVER_ERROR, description = "Unable to delete events")
+    ),
+)]
+#[axum_macros::debug_handler]
+async fn delete_events(
+    Path(repository_name): Path<String>,
+    State(state): State<RepositoryEndpointState>,
+    Json(payload): Json<DeleteEventsRequest>,
+) -> Result<Json<DeleteEventsResponse>, IndexifyAPIError> {
+    let deleted = state
+        .repository_manager
+        .delete_events(&repository_name, payload.ids)
+        .await
+        .map_err(repository_api_error)?;
+    Ok(Json(DeleteEventsResponse { deleted }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/executors",
+    tag = "indexify",
+    responses(
+        (status = 200, description = "List of currently running executors", body = ListExecutorsResponse),
+        (status = INTERNAL_SERVER_ERROR, description = "Unable to load executors")
+    ),
+)]
+#[axum_macros::debug_handler]
+async fn list_executors(
+    State(state): State<RepositoryEndpointState>,
+) -> Result<Json<ListExecutorsResponse>, IndexifyAPIError> {
+    let executors = state
+        .repository_manager
+        .list_executors()
+        .await
+        .map_err(|e| IndexifyAPIError::new(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .into_iter()
+        .map(|e| e.into())
+        .collect();
+    Ok(Json(ListExecutorsResponse { executors }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/extractors",
+    tag = "indexify",
+    params(ListExtractorsRequest),
+    responses(
+        (status = 200, description = "List of extractors available", body = ListExtractorsResponse),
+        (status = INTERNAL_SERVER_ERROR, description = "Unable to search index")
+    ),
+)]
+#[axum_macros::debug_handler]
+async fn list_extractors(
+    State(state): State<RepositoryEndpointState>,
+    Query(query): Query<ListExtractorsRequest>,
+) -> Result<Json<ListExtractorsResponse>, IndexifyAPIError> {
+    let extractors = if query.include_deleted {
+        state
+            .repository_manager
+            .list_extractors_including_deleted()
+            .await
+    } else {
+        state.repository_manager.list_extractors().await
+    };
+    let extractors = extractors
+        .map_err(|e| IndexifyAPIError::new(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .into_iter()
+        .map(|e| e.into())
+        .collect();
+    Ok(Json(ListExtractorsResponse { extractors }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/extractors/{extractor_name}",
+    tag = "indexify",
+    responses(
+        (status = 200, description = "Extractor was deleted", body = DeleteExtractorResponse),
+        (status = 404, description = "Extractor not found"),
+        (status = INTERNAL_SERVER_ERROR, description = "Unable to delete extractor")
+    ),
+)]
+#[axum_macros::debug_handler]
+async fn delete_extractor(
+    Path(extractor_name): Path<String>,
+    State(state): State<RepositoryEndpointState>,
+) -> Result<Json<DeleteExtractorResponse>, IndexifyAPIError> {
+    state
+        .repository_manager
+        .delete_extractor(&extractor_name)
+        .await
+        .map_err(repository_api_error)?;
+    Ok(Json(DeleteExtractorResponse {}))
+}
+
+#[utoipa::path(
+    post,
+    path = "/repository/{repository_name}/search",
+    tag = "indexify",
+    responses(
+        (status = 200, description = "Index search results", body = IndexSearchResponse),
+        (status = INTERNAL_SERVER_ERROR, description = "Unable to search index")
+    ),
+)]
+#[axum_macros::debug_handler]
+async fn index_search(
+    Path(repository_name): Path<String>,
+    State(state): State<RepositoryEndpointState>,
+    Json(query): Json<SearchRequest>,
+) -> Result<Json<IndexSearchResponse>, IndexifyAPIError> {
+    let results = state
+        .repository_manager
+        .search(
+            &repository_name,
+            &query.index,
+            &query.query,
+            query.k.unwrap_or(DEFAULT_SEARCH_LIMIT),
+        )
+        .await
+        .map_err(|e| IndexifyAPIError::new(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let document_fragments: Vec<DocumentFragment> = results
+        .iter()
+        .map(|text| DocumentFragment {
+            text: text.text.text.clone(),
+            metadata: text.text.metadata.clone(),
+            confidence_score: text.confidence_score,
+        })
+        .collect();
+    Ok(Json(IndexSearchResponse {
+        results: document_fragments,
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/search",
+    tag = "indexify",
+    params(ContentSearchRequest),
+    responses(
+        (status = 200, description = "Content of every repository matching the query", body = ContentSearchResponse),
+        (status = INTERNAL_SERVER_ERROR, description = "Unable to search content")
+    ),
+)]
+#[axum_macros::debug_handler]
+async fn search_all_content(
+    State(state): State<RepositoryEndpointState>,
+    Query(query): Query<ContentSearchRequest>,
+) -> Result<Json<ContentSearchResponse>, IndexifyAPIError> {
+    let mut pagination = persistence::Pagination::default();
+    if let Some(offset) = query.offset {
+        pagination.offset = offset;
+    }
+    if let Some(limit) = query.limit {
+        pagination.limit = limit;
+    }
+    let (content_list, total) = state
+        .repository_manager
+        .search_all_content(&query.query, pagination)
+        .await
+        .map_err(|e| IndexifyAPIError::new(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(Json(ContentSearchResponse {
+        results: content_list.into_iter().map(|c| c.into()).collect(),
+        total,
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/repository/{repository_name}/attributes",
+    tag = "indexify",
+    params(AttributeLookupRequest),
+    responses(
+        (status = 200, description = "List of Events in a repository", body = AttributeLookupResponse),
+        (status = INTERNAL_SERVER_ERROR, description = "Unable to list events in repository")
+    ),
+)]
+#[axum_macros::debug_handler]
+async fn attribute_lookup(
+    Path(repository_name): Path<String>,
+    State(state): State<RepositoryEndpointState>,
+    Query(query): Query<AttributeLookupRequest>,
+) -> Result<Json<AttributeLookupResponse>, IndexifyAPIError> {
+    let attributes = state
+        .repository_manager
+        .attribute_lookup(&repository_name, &query.index, query.content_id.as_ref())
         .await
         .map_err(|e| IndexifyAPIError::new(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
@@ -526,6 +1523,76 @@ async fn attribute_lookup(
     }))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/repositories/{repository_name}/attributes",
+    tag = "indexify",
+    params(AttributeLookupRequest),
+    responses(
+        (status = 200, description = "Attributes of the index were deleted", body = DeleteAttributesResponse),
+        (status = INTERNAL_SERVER_ERROR, description = "Unable to delete attributes")
+    ),
+)]
+#[axum_macros::debug_handler]
+async fn delete_attributes(
+    Path(repository_name): Path<String>,
+    State(state): State<RepositoryEndpointState>,
+    Query(query): Query<AttributeLookupRequest>,
+) -> Result<Json<DeleteAttributesResponse>, IndexifyAPIError> {
+    let deleted = state
+        .repository_manager
+        .delete_extracted_attributes(&repository_name, &query.index, query.content_id.as_deref())
+        .await
+        .map_err(repository_api_error)?;
+    Ok(Json(DeleteAttributesResponse { deleted }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/repositories/{repository_name}/content_attributes",
+    request_body = ContentAttributesRequest,
+    tag = "indexify",
+    responses(
+        (status = 200, description = "Attributes of the content, keyed by content id", body = ContentAttributesResponse),
+        (status = INTERNAL_SERVER_ERROR, description = "Unable to fetch attributes")
+    ),
+)]
+#[axum_macros::debug_handler]
+async fn content_attributes(
+    Path(repository_name): Path<String>,
+    State(state): State<RepositoryEndpointState>,
+    Json(payload): Json<ContentAttributesRequest>,
+) -> Result<Json<ContentAttributesResponse>, IndexifyAPIError> {
+    let attributes = state
+        .repository_manager
+        .attributes_for_contents(&repository_name, &payload.content_ids)
+        .await
+        .map_err(repository_api_error)?
+        .into_iter()
+        .map(|(content_id, attributes)| {
+            let attributes = attributes.into_iter().map(|a| a.into()).collect();
+            (content_id, attributes)
+        })
+        .collect();
+    Ok(Json(ContentAttributesResponse { attributes }))
+}
+
+fn repository_api_error(err: DataRepositoryError) -> IndexifyAPIError {
+    let status = match err {
+        DataRepositoryError::Persistence(
+            RepositoryError::RepositoryNotFound(_)
+            | RepositoryError::IndexNotFound(_)
+            | RepositoryError::ExtractorNotFound(_)
+            | RepositoryError::ExtractorBindingNotFound(_)
+            | RepositoryError::ContentNotFound(_),
+        ) => StatusCode::NOT_FOUND,
+        DataRepositoryError::Persistence(RepositoryError::LogicError(_))
+        | DataRepositoryError::NotAllowed(_) => StatusCode::BAD_REQUEST,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    IndexifyAPIError::new(status, err.to_string())
+}
+
 async fn shutdown_signal() {
     let ctrl_c = async {
         signal::ctrl_c()
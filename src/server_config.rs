@@ -5,6 +5,9 @@ use figment::{
 };
 use serde::{Deserialize, Serialize};
 use std::fs;
+use std::path::PathBuf;
+
+use crate::persistence::SearchNormalization;
 
 const OPENAI_DUMMY_KEY: &str = "xxxxx";
 
@@ -83,6 +86,40 @@ pub struct ExecutorConfig {
     pub executor_id: Option<String>,
 }
 
+/// Options of the repository the server, coordinator and executors keep their
+/// state in. All of them can be left out.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", default)]
+pub struct RepositoryConfig {
+    /// How many extractor configs are cached in memory, none are when not set.
+    pub extractor_cache_capacity: Option<usize>,
+    /// How text is normalized for full text search, `None` or `Unaccent`.
+    pub search_normalization: SearchNormalization,
+    /// Whether the language of added content is detected and kept in its `lang`
+    /// metadata.
+    pub detect_language: bool,
+    /// How often a failing vector store call is attempted, 3 times when not set.
+    pub vectordb_max_attempts: Option<u32>,
+    /// The wait before the first retry of a vector store call, doubled after every
+    /// failure. 100ms when not set.
+    pub vectordb_initial_backoff_ms: Option<u64>,
+    /// Content added while the database can't be reached is buffered in a log at
+    /// this path, and added once it's back. Adding content fails instead when not
+    /// set.
+    pub ingest_log_path: Option<PathBuf>,
+    /// Pending work is handed out by the score `priority * work_priority_weight +
+    /// age_secs * work_age_weight`, highest first. The weights are 1 and 0 when
+    /// not set, so that only priority counts.
+    pub work_priority_weight: Option<f64>,
+    pub work_age_weight: Option<f64>,
+    /// The most transactions open at once, further ones wait for one to finish.
+    /// Unbounded when not set.
+    pub max_concurrent_transactions: Option<usize>,
+    /// How long an executor has to finish work assigned to it before the work is
+    /// handed to another one. 600 seconds when not set.
+    pub work_lease_secs: Option<u64>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub struct ServerConfig {
@@ -94,6 +131,12 @@ pub struct ServerConfig {
     pub coordinator_addr: String,
     pub executor_config: ExecutorConfig,
     pub extractors: Vec<Extractor>,
+    #[serde(default)]
+    pub repository_config: RepositoryConfig,
+    /// The coordinator deletes extraction events this long after they were
+    /// processed. They are kept when not set.
+    #[serde(default)]
+    pub extraction_event_retention_secs: Option<u64>,
 }
 
 impl Default for ServerConfig {
@@ -111,6 +154,8 @@ impl Default for ServerConfig {
                 executor_id: None,
             },
             extractors: vec![Extractor::default()],
+            repository_config: RepositoryConfig::default(),
+            extraction_event_retention_secs: None,
         }
     }
 }
@@ -144,7 +189,9 @@ impl ServerConfig {
 
 #[cfg(test)]
 mod tests {
+    use crate::persistence::SearchNormalization;
     use crate::server_config::OPENAI_DUMMY_KEY;
+    use std::path::PathBuf;
 
     #[test]
     fn parse_config() {
@@ -159,5 +206,28 @@ mod tests {
             config.index_config.qdrant_config.unwrap().addr,
             "http://qdrant:6334".to_string()
         );
+        assert_eq!(Some(128), config.repository_config.extractor_cache_capacity);
+        assert_eq!(
+            SearchNormalization::Unaccent,
+            config.repository_config.search_normalization
+        );
+        assert!(config.repository_config.detect_language);
+        assert_eq!(Some(5), config.repository_config.vectordb_max_attempts);
+        assert_eq!(
+            Some(200),
+            config.repository_config.vectordb_initial_backoff_ms
+        );
+        assert_eq!(
+            Some(PathBuf::from("/tmp/indexify-ingest.log")),
+            config.repository_config.ingest_log_path
+        );
+        assert_eq!(Some(1.0), config.repository_config.work_priority_weight);
+        assert_eq!(Some(0.01), config.repository_config.work_age_weight);
+        assert_eq!(
+            Some(16),
+            config.repository_config.max_concurrent_transactions
+        );
+        assert_eq!(Some(300), config.repository_config.work_lease_secs);
+        assert_eq!(Some(604800), config.extraction_event_retention_secs);
     }
 }
@@ -86,3 +86,193 @@ pub mod db_utils {
         Ok(db)
     }
 }
+
+#[cfg(test)]
+pub mod vectordb_utils {
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    use crate::vectordbs::{self, CreateIndexParams, IndexDistance};
+
+    // A vector store which only keeps track of its indexes, their dimensions and
+    // distances, and of the embeddings added to and deleted from them.
+    // `embeddings` and `payloads` hold the vectors and metadata last written for
+    // each chunk, and `batches` the chunks written by each `upsert_batch` call.
+    // `deleted_content` holds the content ids given to `delete_by_content_id`.
+    #[derive(Default)]
+    pub struct FakeVectorDb {
+        pub dims: Mutex<HashMap<String, u64>>,
+        pub distances: Mutex<HashMap<String, IndexDistance>>,
+        pub added: Mutex<HashMap<String, Vec<String>>>,
+        pub embeddings: Mutex<HashMap<String, Vec<f32>>>,
+        pub payloads: Mutex<HashMap<String, serde_json::Value>>,
+        pub batches: Mutex<Vec<Vec<String>>>,
+        pub deleted_content: Mutex<HashMap<String, Vec<String>>>,
+        pub deleted: Mutex<HashMap<String, Vec<String>>>,
+        // The number of upcoming `create_index` calls which fail
+        pub create_failures: Mutex<u32>,
+    }
+
+    #[async_trait::async_trait]
+    impl vectordbs::VectorDb for FakeVectorDb {
+        async fn create_index(
+            &self,
+            index: CreateIndexParams,
+        ) -> Result<(), vectordbs::VectorDbError> {
+            let mut create_failures = self.create_failures.lock().unwrap();
+            if *create_failures > 0 {
+                *create_failures -= 1;
+                return Err(vectordbs::VectorDbError::IndexCreationError(
+                    "unavailable".into(),
+                ));
+            }
+            self.distances
+                .lock()
+                .unwrap()
+                .insert(index.vectordb_index_name.clone(), index.distance);
+            self.dims
+                .lock()
+                .unwrap()
+                .insert(index.vectordb_index_name, index.vector_dim);
+            Ok(())
+        }
+
+        async fn add_embedding(
+            &self,
+            index: &str,
+            chunks: Vec<vectordbs::VectorChunk>,
+        ) -> Result<(), vectordbs::VectorDbError> {
+            let mut embeddings = self.embeddings.lock().unwrap();
+            self.added
+                .lock()
+                .unwrap()
+                .entry(index.to_string())
+                .or_default()
+                .extend(chunks.into_iter().map(|chunk| {
+                    embeddings.insert(chunk.chunk_id.clone(), chunk.embeddings);
+                    chunk.chunk_id
+                }));
+            Ok(())
+        }
+
+        async fn upsert_batch(
+            &self,
+            index: &CreateIndexParams,
+            points: Vec<vectordbs::VectorPoint>,
+        ) -> Result<(), vectordbs::VectorDbError> {
+            let chunk_ids: Vec<String> = points.iter().map(|point| point.0.clone()).collect();
+            let mut embeddings = self.embeddings.lock().unwrap();
+            let mut payloads = self.payloads.lock().unwrap();
+            for (chunk_id, embedding, metadata) in points {
+                embeddings.insert(chunk_id.clone(), embedding);
+                payloads.insert(chunk_id, metadata);
+            }
+            self.added
+                .lock()
+                .unwrap()
+                .entry(index.vectordb_index_name.clone())
+                .or_default()
+                .extend(chunk_ids.iter().cloned());
+            self.batches.lock().unwrap().push(chunk_ids);
+            Ok(())
+        }
+
+        // Scores the chunks added to the index by their dot product with the query
+        async fn search(
+            &self,
+            index: String,
+            query_embedding: Vec<f32>,
+            k: u64,
+        ) -> Result<Vec<vectordbs::SearchResult>, vectordbs::VectorDbError> {
+            let embeddings = self.embeddings.lock().unwrap();
+            let mut results: Vec<vectordbs::SearchResult> = self
+                .added
+                .lock()
+                .unwrap()
+                .get(&index)
+                .into_iter()
+                .flatten()
+                .filter_map(|chunk_id| {
+                    embeddings
+                        .get(chunk_id)
+                        .map(|embedding| vectordbs::SearchResult {
+                            text: String::new(),
+                            chunk_id: chunk_id.clone(),
+                            confidence_score: embedding
+                                .iter()
+                                .zip(&query_embedding)
+                                .map(|(a, b)| a * b)
+                                .sum(),
+                        })
+                })
+                .collect();
+            results.sort_by(|a, b| b.confidence_score.total_cmp(&a.confidence_score));
+            results.truncate(k as usize);
+            Ok(results)
+        }
+
+        async fn delete_embeddings(
+            &self,
+            index: &str,
+            chunk_ids: Vec<String>,
+        ) -> Result<(), vectordbs::VectorDbError> {
+            self.deleted
+                .lock()
+                .unwrap()
+                .entry(index.to_string())
+                .or_default()
+                .extend(chunk_ids);
+            Ok(())
+        }
+
+        async fn delete_by_content_id(
+            &self,
+            index: &str,
+            content_id: &str,
+        ) -> Result<(), vectordbs::VectorDbError> {
+            self.deleted_content
+                .lock()
+                .unwrap()
+                .entry(index.to_string())
+                .or_default()
+                .push(content_id.to_string());
+            Ok(())
+        }
+
+        async fn drop_index(&self, index: String) -> Result<(), vectordbs::VectorDbError> {
+            self.distances.lock().unwrap().remove(&index);
+            self.dims.lock().unwrap().remove(&index);
+            Ok(())
+        }
+
+        async fn num_vectors(&self, _index: &str) -> Result<u64, vectordbs::VectorDbError> {
+            unimplemented!()
+        }
+
+        async fn list_chunk_ids(
+            &self,
+            index: &str,
+        ) -> Result<Vec<String>, vectordbs::VectorDbError> {
+            let deleted = self.deleted.lock().unwrap();
+            let deleted = deleted.get(index);
+            Ok(self
+                .added
+                .lock()
+                .unwrap()
+                .get(index)
+                .into_iter()
+                .flatten()
+                .filter(|chunk_id| !deleted.is_some_and(|deleted| deleted.contains(chunk_id)))
+                .cloned()
+                .collect())
+        }
+
+        async fn index_dim(&self, index: &str) -> Result<Option<u64>, vectordbs::VectorDbError> {
+            Ok(self.dims.lock().unwrap().get(index).copied())
+        }
+
+        fn name(&self) -> String {
+            "fake".into()
+        }
+    }
+}
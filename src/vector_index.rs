@@ -4,8 +4,8 @@ use dashmap::DashMap;
 use crate::{
     extractors::{create_extractor, ExtractedEmbeddings, ExtractorTS},
     index::IndexError,
-    persistence::{Chunk, ExtractorConfig, ExtractorType, Repository, Text},
-    vectordbs::{CreateIndexParams, VectorChunk, VectorDBTS},
+    persistence::{Chunk, ContentType, ExtractorConfig, ExtractorType, Repository, Text},
+    vectordbs::{CreateIndexParams, VectorDBTS},
     ServerConfig,
 };
 use std::sync::Arc;
@@ -24,6 +24,10 @@ pub struct ScoredText {
 }
 
 impl VectorIndexManager {
+    pub fn vector_db(&self) -> VectorDBTS {
+        self.vector_db.clone()
+    }
+
     pub fn new(
         server_config: Arc<ServerConfig>,
         repository: Arc<Repository>,
@@ -57,6 +61,7 @@ impl VectorIndexManager {
                 vector_dim: *dim as u64,
                 distance: distance.clone(),
                 unique_params: None,
+                normalize: false,
             };
             index_params.replace(create_index_params);
         }
@@ -79,23 +84,17 @@ impl VectorIndexManager {
         index: &str,
         embeddings: Vec<ExtractedEmbeddings>,
     ) -> Result<()> {
-        let index_info = self.repository.get_index(index, _repository).await?;
-        let vector_index_name = index_info.vector_index_name.clone().unwrap();
-        let mut vector_chunks = Vec::new();
-        let mut chunks = Vec::new();
-        embeddings.iter().for_each(|embedding| {
-            let chunk = Chunk::new(embedding.text.clone(), embedding.content_id.clone());
-            let vector_chunk = VectorChunk::new(
-                chunk.chunk_id.clone(),
-                chunk.text.clone(),
-                embedding.embeddings.clone(),
-            );
-            chunks.push(chunk);
-            vector_chunks.push(vector_chunk);
-        });
-        self.repository.create_chunks(chunks, index).await?;
-        self.vector_db
-            .add_embedding(&vector_index_name, vector_chunks)
+        let embeddings = embeddings
+            .into_iter()
+            .map(|embedding| {
+                (
+                    Chunk::new(embedding.text, embedding.content_id),
+                    embedding.embeddings,
+                )
+            })
+            .collect();
+        self.repository
+            .store_embeddings(_repository, index, embeddings, self.vector_db.clone())
             .await?;
         Ok(())
     }
@@ -132,6 +131,10 @@ impl VectorIndexManager {
                     id: chunk.as_ref().unwrap().content_id.clone(),
                     text: chunk.as_ref().unwrap().text.clone(),
                     metadata: chunk.as_ref().unwrap().metadata.clone(),
+                    source: None,
+                    external_id: None,
+                    priority: 0,
+                    content_type: ContentType::Text,
                 },
                 confidence_score: result.confidence_score,
             };
@@ -147,12 +150,15 @@ mod tests {
     use std::collections::HashMap;
     use std::env;
 
+    use super::*;
     use crate::data_repository_manager::DataRepositoryManager;
     use crate::persistence::{DataRepository, ExtractorBinding, Text};
     use crate::test_util;
     use crate::test_util::db_utils::{
         create_index_manager, DEFAULT_TEST_EXTRACTOR, DEFAULT_TEST_REPOSITORY,
     };
+    use crate::test_util::vectordb_utils::FakeVectorDb;
+    use crate::vectordbs::IndexDistance;
 
     #[tokio::test]
     #[tracing_test::traced_test]
@@ -212,4 +218,56 @@ mod tests {
             .unwrap();
         assert_eq!(1, result.len())
     }
+
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn test_add_embedding_normalizes_vectors() {
+        let db = test_util::db_utils::create_db().await.unwrap();
+        let repository = Arc::new(Repository::new_with_db(db));
+        let vector_db = Arc::new(FakeVectorDb::default());
+        let server_config = Arc::new(ServerConfig {
+            extractors: vec![],
+            ..Default::default()
+        });
+        let index_manager =
+            VectorIndexManager::new(server_config, repository.clone(), vector_db.clone());
+        for (index, normalize) in [("normalized", true), ("raw", false)] {
+            repository
+                .create_vector_index(
+                    DEFAULT_TEST_REPOSITORY,
+                    DEFAULT_TEST_EXTRACTOR,
+                    index,
+                    CreateIndexParams {
+                        vectordb_index_name: index.into(),
+                        vector_dim: 2,
+                        distance: IndexDistance::Cosine,
+                        unique_params: None,
+                        normalize,
+                    },
+                    vector_db.clone(),
+                )
+                .await
+                .unwrap();
+            index_manager
+                .add_embedding(
+                    DEFAULT_TEST_REPOSITORY,
+                    index,
+                    vec![ExtractedEmbeddings {
+                        content_id: "content".into(),
+                        text: index.into(),
+                        embeddings: vec![3.0, 4.0],
+                    }],
+                )
+                .await
+                .unwrap();
+        }
+
+        let added = vector_db.added.lock().unwrap();
+        let embeddings = vector_db.embeddings.lock().unwrap();
+        let normalized = &embeddings[&added["normalized"][0]];
+        let norm = normalized.iter().map(|x| x * x).sum::<f32>().sqrt();
+        assert!((norm - 1.0).abs() < 1e-6);
+        assert!((normalized[0] / normalized[1] - 0.75).abs() < 1e-6);
+        assert_eq!(vec![3.0, 4.0], embeddings[&added["raw"][0]]);
+    }
 }
@@ -13,7 +13,7 @@ pub mod qdrant;
 
 use qdrant::QdrantDb;
 
-#[derive(Display, Debug, Clone, EnumString, Serialize, Deserialize)]
+#[derive(Display, Debug, Clone, PartialEq, EnumString, Serialize, Deserialize)]
 pub enum IndexDistance {
     #[strum(serialize = "cosine")]
     Cosine,
@@ -30,6 +30,8 @@ pub struct CreateIndexParams {
     pub vector_dim: u64,
     pub distance: IndexDistance,
     pub unique_params: Option<Vec<String>>,
+    /// Whether vectors are scaled to unit length before they are written to the index.
+    pub normalize: bool,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -79,6 +81,18 @@ impl VectorChunk {
     }
 }
 
+/// Scales a vector to unit length. A zero vector is left as it is.
+pub fn normalize(vector: &mut [f32]) {
+    let norm = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        vector.iter_mut().for_each(|x| *x /= norm);
+    }
+}
+
+/// A vector to write to an index: the id of its chunk, its embedding, and the
+/// metadata stored along with it.
+pub type VectorPoint = (String, Vec<f32>, serde_json::Value);
+
 /// A trait that defines the interface for interacting with a vector database.
 /// The vector database is responsible for storing and querying vector embeddings.
 #[async_trait]
@@ -93,6 +107,14 @@ pub trait VectorDb {
         chunks: Vec<VectorChunk>,
     ) -> Result<(), VectorDbError>;
 
+    /// Writes a batch of vectors to the specified index in one round trip,
+    /// replacing the vectors of chunks which are already in it.
+    async fn upsert_batch(
+        &self,
+        index: &CreateIndexParams,
+        points: Vec<VectorPoint>,
+    ) -> Result<(), VectorDbError>;
+
     /// Searches for the nearest neighbors of a query vector in the specified index.
     async fn search(
         &self,
@@ -101,12 +123,34 @@ pub trait VectorDb {
         k: u64,
     ) -> Result<Vec<SearchResult>, VectorDbError>;
 
+    /// Deletes the embeddings of the given chunks from the specified index.
+    async fn delete_embeddings(
+        &self,
+        index: &str,
+        chunk_ids: Vec<String>,
+    ) -> Result<(), VectorDbError>;
+
+    /// Deletes the embeddings of all the chunks of a content from the specified
+    /// index, going by the content id in their metadata.
+    async fn delete_by_content_id(
+        &self,
+        index: &str,
+        content_id: &str,
+    ) -> Result<(), VectorDbError>;
+
     /// Deletes the specified vector index from the vector database.
     async fn drop_index(&self, index: String) -> Result<(), VectorDbError>;
 
     /// Returns the number of vectors in the specified index.
     async fn num_vectors(&self, index: &str) -> Result<u64, VectorDbError>;
 
+    /// Returns the ids of the chunks which have a vector in the specified index.
+    async fn list_chunk_ids(&self, index: &str) -> Result<Vec<String>, VectorDbError>;
+
+    /// Returns the dimension of the vectors in the specified index, or `None` if
+    /// the index doesn't exist.
+    async fn index_dim(&self, index: &str) -> Result<Option<u64>, VectorDbError>;
+
     fn name(&self) -> String;
 }
 
@@ -7,12 +7,14 @@ use qdrant_client::{
     client::QdrantClient,
     client::{Payload, QdrantClientConfig},
     qdrant::{
-        vectors_config::Config, with_payload_selector::SelectorOptions, CreateCollection, Distance,
-        PointStruct, SearchPoints, VectorParams, VectorsConfig, WithPayloadSelector,
+        point_id::PointIdOptions, r#match::MatchValue, vectors_config::Config,
+        with_payload_selector::SelectorOptions, CreateCollection, Distance, FieldCondition, Filter,
+        Match, PointId, PointStruct, ScrollPoints, SearchPoints, VectorParams, VectorsConfig,
+        WithPayloadSelector,
     },
 };
 
-use super::{CreateIndexParams, VectorDb, VectorDbError};
+use super::{CreateIndexParams, VectorDb, VectorDbError, VectorPoint};
 use crate::{
     vectordbs::{IndexDistance, SearchResult, VectorChunk},
     QdrantConfig,
@@ -22,7 +24,6 @@ fn hex_to_u64(hex: &str) -> Result<u64, std::num::ParseIntError> {
     u64::from_str_radix(hex, 16)
 }
 
-#[allow(dead_code)]
 fn u64_to_hex(number: u64) -> String {
     format!("{:x}", number)
 }
@@ -124,6 +125,31 @@ impl VectorDb for QdrantDb {
         Ok(())
     }
 
+    async fn upsert_batch(
+        &self,
+        index: &CreateIndexParams,
+        points: Vec<VectorPoint>,
+    ) -> Result<(), VectorDbError> {
+        let mut point_structs = Vec::with_capacity(points.len());
+        for (chunk_id, embedding, metadata) in points {
+            let point_id =
+                hex_to_u64(&chunk_id).map_err(|e| VectorDbError::IndexWriteError(e.to_string()))?;
+            // The text of a chunk is kept with the chunk rather than in the index
+            let payload = Payload::try_from(json!(QdrantPayload {
+                text: String::new(),
+                chunk_id,
+                metadata,
+            }))
+            .map_err(|e| VectorDbError::IndexWriteError(e.to_string()))?;
+            point_structs.push(PointStruct::new(point_id, embedding, payload));
+        }
+        self.create_client()?
+            .upsert_points(&index.vectordb_index_name, point_structs, None)
+            .await
+            .map_err(|e| VectorDbError::IndexWriteError(e.to_string()))?;
+        Ok(())
+    }
+
     async fn search(
         &self,
         index: String,
@@ -158,6 +184,46 @@ impl VectorDb for QdrantDb {
         Ok(documents)
     }
 
+    async fn delete_embeddings(
+        &self,
+        index: &str,
+        chunk_ids: Vec<String>,
+    ) -> Result<(), VectorDbError> {
+        let point_ids = chunk_ids
+            .iter()
+            .map(|chunk_id| hex_to_u64(chunk_id).map(PointId::from))
+            .collect::<Result<Vec<PointId>, _>>()
+            .map_err(|e| VectorDbError::IndexWriteError(e.to_string()))?;
+        self.create_client()?
+            .delete_points(index, &point_ids.into(), None)
+            .await
+            .map_err(|e| VectorDbError::IndexWriteError(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn delete_by_content_id(
+        &self,
+        index: &str,
+        content_id: &str,
+    ) -> Result<(), VectorDbError> {
+        let filter = Filter {
+            must: vec![FieldCondition {
+                key: "metadata.content_id".into(),
+                r#match: Some(Match {
+                    match_value: Some(MatchValue::Keyword(content_id.into())),
+                }),
+                ..Default::default()
+            }
+            .into()],
+            ..Default::default()
+        };
+        self.create_client()?
+            .delete_points(index, &filter.into(), None)
+            .await
+            .map_err(|e| VectorDbError::IndexWriteError(e.to_string()))?;
+        Ok(())
+    }
+
     async fn drop_index(&self, index: String) -> Result<(), VectorDbError> {
         let result = self.create_client()?.delete_collection(index.clone()).await;
         if let Err(err) = result {
@@ -180,6 +246,64 @@ impl VectorDb for QdrantDb {
             .ok_or(VectorDbError::IndexReadError("index not found".into()))?;
         Ok(collection_info.points_count)
     }
+
+    async fn list_chunk_ids(&self, index: &str) -> Result<Vec<String>, VectorDbError> {
+        let client = self.create_client()?;
+        let mut chunk_ids = Vec::new();
+        let mut offset = None;
+        loop {
+            let response = client
+                .scroll(&ScrollPoints {
+                    collection_name: index.into(),
+                    offset,
+                    limit: Some(1000),
+                    with_payload: Some(WithPayloadSelector {
+                        selector_options: Some(SelectorOptions::Enable(false)),
+                    }),
+                    ..Default::default()
+                })
+                .await
+                .map_err(|e| VectorDbError::IndexReadError(e.to_string()))?;
+            for point in response.result {
+                if let Some(PointIdOptions::Num(id)) = point.id.and_then(|id| id.point_id_options) {
+                    chunk_ids.push(u64_to_hex(id));
+                }
+            }
+            offset = response.next_page_offset;
+            if offset.is_none() {
+                break;
+            }
+        }
+        Ok(chunk_ids)
+    }
+
+    async fn index_dim(&self, index: &str) -> Result<Option<u64>, VectorDbError> {
+        let client = self.create_client()?;
+        let exists = client
+            .has_collection(index)
+            .await
+            .map_err(|e| VectorDbError::IndexReadError(e.to_string()))?;
+        if !exists {
+            return Ok(None);
+        }
+        let result = client
+            .collection_info(index)
+            .await
+            .map_err(|e| VectorDbError::IndexReadError(e.to_string()))?;
+        let vectors_config = result
+            .result
+            .and_then(|info| info.config)
+            .and_then(|config| config.params)
+            .and_then(|params| params.vectors_config)
+            .and_then(|vectors_config| vectors_config.config);
+        match vectors_config {
+            Some(Config::Params(params)) => Ok(Some(params.size)),
+            _ => Err(VectorDbError::IndexReadError(format!(
+                "index `{}` doesn't have a single vector size",
+                index
+            ))),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -188,7 +312,7 @@ mod tests {
 
     use crate::vectordbs::{IndexDistance, VectorChunk, VectorDBTS};
 
-    use super::{CreateIndexParams, QdrantDb};
+    use super::{CreateIndexParams, Distance, QdrantDb};
 
     #[tokio::test]
     #[tracing_test::traced_test]
@@ -203,6 +327,7 @@ mod tests {
                 vector_dim: 2,
                 distance: IndexDistance::Cosine,
                 unique_params: None,
+                normalize: false,
             })
             .await
             .unwrap();
@@ -238,6 +363,7 @@ mod tests {
                 vector_dim: 2,
                 distance: IndexDistance::Cosine,
                 unique_params: Some(hash_on.clone()),
+                normalize: false,
             })
             .await
             .unwrap();
@@ -255,4 +381,17 @@ mod tests {
 
         assert_eq!(num_elements, 1);
     }
+
+    #[test]
+    fn test_to_distance() {
+        assert_eq!(
+            Distance::Cosine,
+            QdrantDb::to_distance(IndexDistance::Cosine)
+        );
+        assert_eq!(Distance::Dot, QdrantDb::to_distance(IndexDistance::Dot));
+        assert_eq!(
+            Distance::Euclid,
+            QdrantDb::to_distance(IndexDistance::Euclidean)
+        );
+    }
 }